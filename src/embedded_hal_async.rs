@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: Copyright The arm-generic-timer Contributors.
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Implementation of `embedded-hal-async` traits for timers.
+//!
+//! The delay arms the timer with its interrupt masked and polls the timer condition, waking its own
+//! task after each check. The executor keeps polling the delay until the deadline passes, so no
+//! interrupt handler is needed. The delay takes over the compare value and control register of the
+//! timer, and leaves the timer disabled when it completes.
+
+use core::{future::poll_fn, task::Poll, time::Duration};
+
+use crate::{Timer, TimerInterface};
+use embedded_hal_async::delay::DelayNs;
+
+impl<T: TimerInterface> Timer<T> {
+    /// Waits asynchronously until `duration` has passed.
+    async fn wait_async(&mut self, duration: Duration) {
+        self.arm(duration);
+
+        poll_fn(|cx| {
+            if self.is_expired() {
+                self.disable();
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+impl<T: TimerInterface> DelayNs for Timer<T> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.wait_async(Duration::from_nanos(ns.into())).await;
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        self.wait_async(Duration::from_micros(us.into())).await;
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.wait_async(Duration::from_millis(ms.into())).await;
+    }
+}
@@ -9,11 +9,132 @@
 
 #[cfg(feature = "embedded-hal")]
 mod embedded_hal;
+#[cfg(feature = "embedded-hal-async")]
+mod embedded_hal_async;
 pub mod memory_mapped;
 #[cfg(any(test, feature = "fakes", target_arch = "aarch64"))]
 pub mod sysreg;
 
-use core::{hint::spin_loop, time::Duration};
+use core::{fmt, hint::spin_loop, num::NonZeroU32, time::Duration};
+use memory_mapped::TimerControl;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+/// Errors returned by the fallible timer operations.
+///
+/// New variants may be added as the driver grows, so matches on this type need a wildcard arm.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TimerError {
+    /// The frequency table has more entries than the available `CNTFID` registers.
+    FrequencyTableTooLong,
+    /// The selected entry of the frequency modes table is not populated.
+    FrequencyModeUnavailable,
+    /// The frequency table has non-zero entries after the zero entry terminating it.
+    FrequencyTableGap,
+    /// Counter scaling was requested but FEAT_CNTSC is not implemented.
+    ScalingUnsupported,
+    /// The deadline is too close to the current count to be armed safely.
+    DeadlineTooClose,
+    /// The Counter ID registers do not identify a valid component.
+    ComponentMismatch,
+    /// The deadline is beyond the largest representable compare value.
+    DeadlineOverflow,
+    /// The counter did not acknowledge the requested frequency mode in time.
+    FrequencyNotAcknowledged,
+    /// The timer frame index is out of range.
+    FrameOutOfRange,
+    /// The counter did not advance by the expected number of ticks.
+    CounterStalled,
+    /// The counter ran backwards.
+    CounterRanBackwards,
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::FrequencyTableTooLong => "frequency table too long",
+            Self::FrequencyModeUnavailable => "frequency mode unavailable",
+            Self::FrequencyTableGap => "frequency table has entries after its terminator",
+            Self::ScalingUnsupported => "counter scaling not supported",
+            Self::DeadlineTooClose => "deadline too close to the current count",
+            Self::ComponentMismatch => "counter ID does not identify a valid component",
+            Self::DeadlineOverflow => "deadline overflows the compare value",
+            Self::FrequencyNotAcknowledged => "frequency change not acknowledged",
+            Self::FrameOutOfRange => "timer frame index out of range",
+            Self::CounterStalled => "counter not incrementing",
+            Self::CounterRanBackwards => "counter ran backwards",
+        })
+    }
+}
+
+impl core::error::Error for TimerError {}
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Converts counter ticks to `Duration` using the given counter frequency in Hz. The result is
+/// truncated to whole nanoseconds.
+///
+/// Panics if `frequency` is zero.
+pub fn ticks_to_duration(ticks: u64, frequency: u32) -> Duration {
+    let frequency = u64::from(frequency);
+    let nanos = u128::from(ticks % frequency) * NANOS_PER_SEC / u128::from(frequency);
+
+    Duration::new(ticks / frequency, nanos as u32)
+}
+
+/// Converts counter ticks to microseconds using the given counter frequency in Hz.
+///
+/// The result is truncated to whole microseconds, i.e. the sub-microsecond remainder is dropped
+/// rather than rounded. It saturates at `u64::MAX`, which can only happen for frequencies below
+/// 1 MHz.
+///
+/// Panics if `frequency` is zero.
+pub fn ticks_to_micros(ticks: u64, frequency: u32) -> u64 {
+    let micros = u128::from(ticks) * 1_000_000 / u128::from(frequency);
+
+    u64::try_from(micros).unwrap_or(u64::MAX)
+}
+
+/// Converts `Duration` to counter ticks using the given counter frequency in Hz, saturating at
+/// `u64::MAX`. The result is rounded to the nearest tick.
+pub(crate) fn duration_to_ticks(duration: Duration, frequency: u32) -> u64 {
+    let ticks = (u128::from(frequency) * duration.as_nanos() + NANOS_PER_SEC / 2) / NANOS_PER_SEC;
+
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+/// Converts `Duration` to counter ticks using the given counter frequency in Hz, saturating at
+/// `u64::MAX`. The result is rounded up to the next whole tick, so waiting for the ticks never
+/// takes less than `duration`.
+pub(crate) fn duration_to_ticks_ceil(duration: Duration, frequency: u32) -> u64 {
+    let ticks = (u128::from(frequency) * duration.as_nanos()).div_ceil(NANOS_PER_SEC);
+
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+/// Source of the counter frequency.
+pub trait CounterFrequency {
+    /// Returns the counter frequency in Hz.
+    fn frequency(&self) -> u32;
+
+    /// Returns the counter frequency in Hz, or `None` if the frequency has not been programmed.
+    fn frequency_nonzero(&self) -> Option<NonZeroU32> {
+        NonZeroU32::new(self.frequency())
+    }
+
+    /// Converts counter ticks to `Duration` using the counter frequency, see [`ticks_to_duration`].
+    ///
+    /// Panics if the frequency is zero.
+    fn ticks_to_duration(&self, ticks: u64) -> Duration {
+        ticks_to_duration(ticks, self.frequency())
+    }
+
+    /// Converts `Duration` to counter ticks using the counter frequency, rounding to the nearest
+    /// tick and saturating at `u64::MAX`.
+    fn duration_to_ticks(&self, duration: Duration) -> u64 {
+        duration_to_ticks(duration, self.frequency())
+    }
+}
 
 /// Interface for accessing common timer registers.
 pub trait TimerInterface {
@@ -25,17 +146,207 @@ pub trait TimerInterface {
 
     /// Returns the down-counter value.
     fn timer_value(&self) -> u32;
+
+    /// Sets the down-counter value. The hardware sets the compare value to the current count plus
+    /// the sign-extended timer value.
+    fn set_timer_value(&mut self, value: u32);
+
+    /// Returns the count value against which the compare value is evaluated.
+    fn count(&self) -> u64;
+
+    /// Returns the compare value.
+    fn compare_value(&self) -> u64;
+
+    /// Sets the compare value.
+    fn set_compare_value(&mut self, value: u64);
+
+    /// Returns the value of the control register.
+    fn control(&self) -> TimerControl;
+
+    /// Sets the value of the control register.
+    fn set_control(&mut self, control: TimerControl);
+}
+
+/// Snapshot of the registers of a timer, captured by [`Timer::snapshot`].
+#[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct TimerRegsSnapshot {
+    /// Compare value.
+    pub compare_value: u64,
+    /// Down-counter value at the time of capturing the snapshot.
+    pub timer_value: u32,
+    /// Control register value.
+    pub control: TimerControl,
+}
+
+/// Point in time, represented by a count value of a counter and the frequency of the counter.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Instant {
+    count: u64,
+    frequency: u32,
+    max_count: u64,
+}
+
+impl Instant {
+    /// Creates new instance from a count value and the counter frequency in Hz, for a 64-bit
+    /// counter.
+    pub const fn new(count: u64, frequency: u32) -> Self {
+        Self::with_max_count(count, frequency, u64::MAX)
+    }
+
+    /// Creates new instance from a count value and the counter frequency in Hz, for a counter which
+    /// rolls over to zero after reaching `max_count`. `max_count` must be one less than a power of
+    /// two, e.g. [`GenericTimerControl::max_count`](memory_mapped::GenericTimerControl::max_count).
+    pub const fn with_max_count(count: u64, frequency: u32, max_count: u64) -> Self {
+        Self {
+            count,
+            frequency,
+            max_count,
+        }
+    }
+
+    /// Returns the count value.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the counter frequency in Hz.
+    pub const fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`.
+    ///
+    /// Both instants must originate from the same counter, and less than a full wrap-around of the
+    /// counter must have occurred between them. Panics if the frequency is zero.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        ticks_to_duration(self.ticks_since(earlier), self.frequency)
+    }
+
+    /// Returns the number of whole periods elapsed from `base` to `self`.
+    ///
+    /// Both instants must originate from the same counter, and less than a full wrap-around of the
+    /// counter must have occurred between them. The calculation is exact, i.e. the period is not
+    /// rounded to whole ticks. Panics if the period or the frequency is zero.
+    pub fn periods_since(&self, base: Instant, period: Duration) -> u64 {
+        let ticks = u128::from(self.ticks_since(base));
+        let periods = ticks * NANOS_PER_SEC / (period.as_nanos() * u128::from(self.frequency));
+
+        periods as u64
+    }
+
+    /// Calculates the number of ticks from `earlier` to `self`, taking a single rollover of the
+    /// counter into account.
+    fn ticks_since(&self, earlier: Instant) -> u64 {
+        self.count.wrapping_sub(earlier.count) & self.max_count
+    }
+}
+
+/// Linear mapping of timestamps from a foreign time domain, e.g. a network clock, to the count
+/// value of the local counter.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TimeDomainMap {
+    /// Local count value at the anchor point.
+    pub local_anchor_count: u64,
+    /// Foreign timestamp at the anchor point.
+    pub foreign_anchor: u64,
+    /// Number of local ticks per foreign timestamp unit, as a numerator and denominator pair.
+    pub rate_ratio: (u32, u32),
+}
+
+impl TimeDomainMap {
+    /// Converts a foreign timestamp to the corresponding local count value.
+    ///
+    /// Timestamps before the foreign anchor are supported. The result is truncated towards the
+    /// anchor and saturates at 0 and `u64::MAX`. Panics if the denominator of the rate ratio is
+    /// zero.
+    pub fn to_local_count(self, foreign_timestamp: u64) -> u64 {
+        let (numerator, denominator) = self.rate_ratio;
+        let foreign_delta = i128::from(foreign_timestamp) - i128::from(self.foreign_anchor);
+        let local_delta = foreign_delta * i128::from(numerator) / i128::from(denominator);
+        let local_count = i128::from(self.local_anchor_count) + local_delta;
+
+        local_count.clamp(0, u64::MAX.into()) as u64
+    }
+}
+
+/// Token identifying a deadline armed by [`Timer::arm_after_token`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct TimerToken {
+    compare_value: u64,
+}
+
+/// Period of a periodic timer, which might not be a whole number of counter ticks.
+#[derive(Clone, Copy, Debug)]
+struct Period {
+    /// Whole ticks of the period.
+    ticks: u64,
+    /// Fractional tick of the period, in units of `1 / divisor` ticks.
+    remainder: u64,
+    divisor: u64,
+    /// Accumulated fractional ticks, in units of `1 / divisor` ticks.
+    accumulator: u64,
+}
+
+impl Period {
+    /// Creates the period of `duration` at the counter frequency `frequency` in Hz, with an empty
+    /// accumulator.
+    fn from_duration(duration: Duration, frequency: u32) -> Self {
+        let scaled = u128::from(frequency) * duration.as_nanos();
+
+        Self {
+            ticks: u64::try_from(scaled / NANOS_PER_SEC).unwrap_or(u64::MAX),
+            remainder: (scaled % NANOS_PER_SEC) as u64,
+            divisor: NANOS_PER_SEC as u64,
+            accumulator: 0,
+        }
+    }
+
+    /// Returns the number of ticks to the next deadline, adding an extra tick whenever the
+    /// accumulated fractional ticks reach a whole tick.
+    fn next_increment(&mut self) -> u64 {
+        self.increment_by(1)
+    }
+
+    /// Returns the number of ticks to the deadline `count` periods ahead, accumulating the
+    /// fractional ticks like [`Period::next_increment`].
+    fn increment_by(&mut self, count: u64) -> u64 {
+        let divisor = u128::from(self.divisor);
+        let fraction =
+            u128::from(self.accumulator) + u128::from(self.remainder) * u128::from(count);
+        self.accumulator = (fraction % divisor) as u64;
+
+        self.ticks
+            .wrapping_mul(count)
+            .wrapping_add((fraction / divisor) as u64)
+    }
 }
 
 /// Generic timer object allowing blocking wait and interrupt enablement.
 pub struct Timer<T: TimerInterface> {
     timer: T,
+    watchdog_timeout: Option<u64>,
+    period: Option<Period>,
+    advance_fraction: u64,
 }
 
 impl<T: TimerInterface> Timer<T> {
+    /// Delay which is conservatively longer than the time it takes to program the timer.
+    const MINIMUM_SAFE_DELAY: Duration = Duration::from_micros(1);
+
     /// Creates new instance.
+    ///
+    /// The frequency of the timer must be programmed, i.e. non-zero, before any waits or deadlines
+    /// are set. Otherwise every duration is converted to zero ticks, which makes waits return
+    /// immediately. This is checked by a debug assertion.
     pub fn new(timer: T) -> Self {
-        Self { timer }
+        Self {
+            timer,
+            watchdog_timeout: None,
+            period: None,
+            advance_fraction: 0,
+        }
     }
 
     /// Enables timer.
@@ -43,31 +354,610 @@ impl<T: TimerInterface> Timer<T> {
         self.timer.enable();
     }
 
-    /// Blocking waits for a duration or maximal possible timer. The timer must be enabled before
-    /// calling wait.
-    pub fn wait(&self, duration: Duration) {
-        let ticks =
-            u128::from(self.timer.frequency()).saturating_mul(duration.as_micros()) / 1_000_000;
-        let increment = u32::try_from(ticks).unwrap_or(u32::MAX);
+    /// Checks whether the timer is enabled, i.e. the ENABLE bit of the control register is set.
+    pub fn enabled(&self) -> bool {
+        self.timer.control().contains(TimerControl::ENABLE)
+    }
 
-        let start = self.timer.timer_value();
+    /// Checks whether the timer interrupt is masked, i.e. the IMASK bit of the control register is
+    /// set.
+    pub fn interrupt_masked(&self) -> bool {
+        self.timer.control().contains(TimerControl::IMASK)
+    }
+
+    /// Checks whether the timer condition is met, i.e. the ISTATUS bit of the control register is
+    /// set. The bit is only valid while the timer is enabled.
+    ///
+    /// Like [`Timer::enabled`] and [`Timer::interrupt_masked`], this returns the raw bit of the
+    /// control register. A disabled timer may keep a stale ISTATUS, so use [`Timer::is_expired`],
+    /// which also requires the timer to be enabled, to check whether the deadline has passed.
+    pub fn condition_met(&self) -> bool {
+        self.timer.control().contains(TimerControl::ISTATUS)
+    }
+
+    /// Sets or clears the ENABLE bit of the control register, leaving the interrupt mask unchanged.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.modify_control(TimerControl::ENABLE, enabled);
+    }
+
+    /// Sets or clears the IMASK bit of the control register, leaving the enable bit unchanged. This
+    /// allows arming a timer with its interrupt masked and unmasking it later.
+    pub fn set_interrupt_masked(&mut self, masked: bool) {
+        self.modify_control(TimerControl::IMASK, masked);
+    }
+
+    /// Sets or clears `flag` in the control register. The read-only ISTATUS bit is not written.
+    fn modify_control(&mut self, flag: TimerControl, value: bool) {
+        let mut control = self.timer.control().difference(TimerControl::ISTATUS);
+        control.set(flag, value);
+        self.timer.set_control(control);
+    }
+
+    /// Blocking waits for a duration. The timer must be enabled before calling wait.
+    ///
+    /// The elapsed ticks are accumulated from the 32-bit down-counter, so durations longer than the
+    /// range of the down-counter are supported.
+    pub fn wait(&self, duration: Duration) {
+        let ticks = self.deadline_ticks(duration);
+        let mut elapsed = 0u64;
+        let mut previous = self.timer.timer_value();
 
         // The timer is a down-counter
-        while start.wrapping_sub(self.timer.timer_value()) < increment {
+        while elapsed < ticks {
             spin_loop();
+
+            let current = self.timer.timer_value();
+            elapsed = elapsed.saturating_add(previous.wrapping_sub(current).into());
+            previous = current;
+        }
+    }
+
+    /// Waits for a duration in a low-power state, executing `wfi` until the deadline passes.
+    ///
+    /// The timer is armed with its interrupt unmasked, so the timer interrupt must be routed and
+    /// enabled in the interrupt controller such that it wakes the core from `wfi`. On each wake-up
+    /// the count is compared against the deadline to handle spurious wake-ups by other interrupts.
+    /// The compare value of the timer is overwritten and the timer is disabled on return.
+    ///
+    /// The deadline is checked against the count rather than the timer condition, so an interrupt
+    /// handler which acknowledges the timer interrupt, e.g. with [`Timer::handle_one_shot`], does
+    /// not leave the core waiting forever. Interrupt handlers must not disable or reprogram the
+    /// timer before the deadline though, as no other wake-up would be guaranteed. Alternatively the
+    /// caller may mask IRQs at PSTATE while waiting: `wfi` still wakes up on a pending interrupt,
+    /// which is then taken once the caller unmasks IRQs again.
+    #[cfg(target_arch = "aarch64")]
+    pub fn wait_low_power(&mut self, duration: Duration) {
+        let ticks = self.deadline_ticks(duration);
+        let compare_value = self.base_count().saturating_add(ticks);
+        self.timer.set_compare_value(compare_value);
+        self.timer
+            .set_control(TimerControl::enabled_with_interrupt());
+
+        while !self.count_reached(compare_value) {
+            // SAFETY: `wfi` only suspends execution until a wake-up event, it has no effect on
+            // memory or registers.
+            unsafe {
+                core::arch::asm!("wfi", options(nomem, nostack, preserves_flags));
+            }
         }
+
+        self.disable();
+    }
+
+    /// Checks whether the count has reached `compare_value`, which is the timer condition evaluated
+    /// independently of the control register.
+    #[cfg(any(target_arch = "aarch64", test))]
+    fn count_reached(&self, compare_value: u64) -> bool {
+        self.base_count() >= compare_value
+    }
+
+    /// Disables the timer. The timer condition is not evaluated and no interrupt is signalled while
+    /// the timer is disabled.
+    ///
+    /// This differs from masking the interrupt with [`TimerControl::IMASK`], which only stops the
+    /// interrupt from being signalled. A masked timer keeps evaluating its condition and ISTATUS
+    /// still reports it, so disabling is the way to cancel a deadline entirely.
+    pub fn disable(&mut self) {
+        self.timer.set_control(TimerControl::empty());
+    }
+
+    /// Returns the timer to a known state: the timer is disabled with its interrupt unmasked and
+    /// the compare value is cleared. The watchdog and periodic configuration of a previous arming
+    /// is also dropped.
+    ///
+    /// The timer value is derived from the compare value, so it is not written separately.
+    pub fn reset(&mut self) {
+        self.timer.set_control(TimerControl::empty());
+        self.timer.set_compare_value(0);
+        self.watchdog_timeout = None;
+        self.period = None;
+    }
+
+    /// Arms the timer to fire once after `duration`, with the interrupt unmasked.
+    ///
+    /// The timer condition remains met after the deadline has passed, so the interrupt keeps being
+    /// signalled until the timer is disabled or re-armed. To make the timer fire only once, the
+    /// interrupt handler must call [`Timer::handle_one_shot`].
+    pub fn arm_one_shot(&mut self, duration: Duration) {
+        let compare_value = self.deadline_after(duration);
+        self.arm_at(compare_value);
+    }
+
+    /// Handles the interrupt of a timer armed by [`Timer::arm_one_shot`] by disabling the timer,
+    /// which also deasserts the timer condition and the interrupt.
+    pub fn handle_one_shot(&mut self) {
+        self.disable();
+    }
+
+    /// Arms the timer as a periodic tick source firing `rate_hz` times per second, with the
+    /// interrupt unmasked. The first deadline is one period after `now`, the following ones are
+    /// programmed by calling [`Timer::rearm`] from the interrupt handler.
+    ///
+    /// If the counter frequency is not a multiple of `rate_hz` the period is not a whole number of
+    /// ticks. The fractional tick is accumulated across rearms and an extra tick is added to the
+    /// period whenever it adds up to a whole tick. The individual periods jitter by one tick, but
+    /// the deadlines do not drift from the exact rate.
+    ///
+    /// Panics if `rate_hz` is zero.
+    pub fn arm_periodic_hz(&mut self, rate_hz: u32, now: u64) {
+        let frequency = u64::from(self.timer.frequency());
+        let rate = u64::from(rate_hz);
+
+        self.arm_period(
+            Period {
+                ticks: frequency / rate,
+                remainder: frequency % rate,
+                divisor: rate,
+                accumulator: 0,
+            },
+            now,
+        );
+    }
+
+    /// Arms the timer as a periodic tick source firing every `period`, with the interrupt unmasked.
+    /// The first deadline is one period after `now`, the following ones are programmed by calling
+    /// [`Timer::rearm`] from the interrupt handler.
+    ///
+    /// Like [`Timer::arm_periodic_hz`], the fractional tick of a period which is not a whole number
+    /// of ticks is accumulated across rearms, so the deadlines do not drift from the exact period.
+    pub fn arm_periodic(&mut self, period: Duration, now: u64) {
+        self.arm_period(Period::from_duration(period, self.timer.frequency()), now);
+    }
+
+    /// Programs the first deadline of `period` after `now` and stores the period for rearming.
+    fn arm_period(&mut self, mut period: Period, now: u64) {
+        let compare_value = now.wrapping_add(period.next_increment());
+        self.period = Some(period);
+        self.arm_at(compare_value);
+    }
+
+    /// Returns how far the current deadline of a periodic timer is from the exact schedule, in
+    /// nanoseconds. The deadlines are whole ticks, so they run early by the accumulated fractional
+    /// tick, which is always less than one tick. Negative values mean the deadline is early.
+    ///
+    /// Returns 0 if the timer has not been armed as a periodic timer.
+    pub fn periodic_drift(&self) -> i64 {
+        let Some(period) = &self.period else {
+            return 0;
+        };
+
+        let frequency = u128::from(self.timer.frequency());
+        if frequency == 0 {
+            return 0;
+        }
+
+        let early = u128::from(period.accumulator) * NANOS_PER_SEC
+            / (u128::from(period.divisor) * frequency);
+
+        // Less than a tick, which is at most a second.
+        -(early as i64)
+    }
+
+    /// Arms the next deadline of a periodic timer, one period after the previous deadline. The
+    /// deadlines are relative to each other, so the interrupt latency does not cause drift. Does
+    /// nothing if the timer has not been armed as a periodic timer.
+    pub fn rearm(&mut self) {
+        let Some(period) = self.period.as_mut() else {
+            return;
+        };
+
+        let increment = period.next_increment();
+        self.arm_at(self.timer.compare_value().wrapping_add(increment));
+    }
+
+    /// Moves the deadline `period` after the current compare value, rather than after the current
+    /// count, which gives a drift-free periodic schedule. The control register is not changed.
+    ///
+    /// Like [`Timer::arm_periodic`], the fractional tick of a period which is not a whole number of
+    /// ticks is carried over to the next advance, so the deadlines jitter by one tick but do not
+    /// drift from the exact schedule.
+    ///
+    /// If the handler runs so late that the new deadline has already passed, the timer condition
+    /// stays met and the interrupt fires again immediately, so missed ticks are caught up one by
+    /// one. Use [`Timer::advance_deadline_skipping`] to skip them instead.
+    pub fn advance_deadline(&mut self, period: Duration) {
+        let mut period = self.advance_period(period);
+        let increment = period.next_increment();
+        self.advance_fraction = period.accumulator;
+
+        self.timer
+            .set_compare_value(self.timer.compare_value().wrapping_add(increment));
+    }
+
+    /// Moves the deadline by whole multiples of `period` after the current compare value, to the
+    /// first deadline which is still ahead of the current count. The control register is not
+    /// changed.
+    ///
+    /// Unlike [`Timer::advance_deadline`], ticks missed by a late handler are skipped rather than
+    /// caught up, while the deadlines stay on the same schedule. The fractional tick is carried
+    /// over in the same way. Returns the number of skipped ticks.
+    pub fn advance_deadline_skipping(&mut self, period: Duration) -> u64 {
+        let mut period = self.advance_period(period);
+        let compare_value = self.timer.compare_value();
+        let now = self.base_count();
+        let behind = now.wrapping_sub(compare_value);
+
+        let mut skipped = if (behind as i64) < 0 {
+            0
+        } else {
+            let exact = u128::from(period.ticks) * u128::from(period.divisor)
+                + u128::from(period.remainder);
+            (u128::from(behind) * u128::from(period.divisor) / exact) as u64
+        };
+
+        let mut increment = period.increment_by(skipped.wrapping_add(1));
+        // The carried fractional tick may leave the deadline exactly at the current count.
+        while (compare_value.wrapping_add(increment).wrapping_sub(now) as i64) <= 0 {
+            increment = increment.wrapping_add(period.next_increment());
+            skipped += 1;
+        }
+
+        self.advance_fraction = period.accumulator;
+        self.timer
+            .set_compare_value(compare_value.wrapping_add(increment));
+
+        skipped
+    }
+
+    /// Converts `period` for advancing the deadline, continuing with the fractional tick left over
+    /// by the previous advance. A period shorter than a tick is extended to one tick.
+    fn advance_period(&self, period: Duration) -> Period {
+        let frequency = self.timer.frequency();
+        debug_assert_ne!(
+            0, frequency,
+            "Timer frequency is zero, CNTFRQ is not programmed"
+        );
+
+        let mut period = Period::from_duration(period, frequency);
+        if period.ticks == 0 {
+            period.ticks = 1;
+            period.remainder = 0;
+        }
+        period.accumulator = self.advance_fraction;
+
+        period
+    }
+
+    /// Checks that the compare value has moved past `previous_cval`, e.g. before re-enabling the
+    /// timer in an interrupt handler. A handler which re-enables the timer without advancing the
+    /// compare value leaves the timer condition met, which retriggers the interrupt immediately and
+    /// hangs the system in an interrupt storm.
+    ///
+    /// Panics if the compare value hasn't advanced and debug assertions are enabled, otherwise it
+    /// does nothing.
+    pub fn assert_deadline_advanced(&self, previous_cval: u64) {
+        if cfg!(debug_assertions) {
+            let compare_value = self.timer.compare_value();
+            assert!(
+                (compare_value.wrapping_sub(previous_cval) as i64) > 0,
+                "Timer deadline not advanced: compare value {compare_value:#x}, previous \
+                {previous_cval:#x}"
+            );
+        }
+    }
+
+    /// Gets the count which the compare value of this timer is evaluated against, i.e. the
+    /// physical count for physical timers and the virtual count for virtual timers. This is the
+    /// current time as seen by the timer, and the base of all relative deadlines.
+    pub fn base_count(&self) -> u64 {
+        self.timer.count()
+    }
+
+    /// Returns the time elapsed since `start`, a count sampled earlier by [`Timer::base_count`].
+    /// The result is truncated to whole nanoseconds.
+    ///
+    /// Less than a full wrap-around of the 64-bit count must have occurred since `start`. Panics if
+    /// the frequency of the timer is zero.
+    pub fn elapsed_since(&self, start: u64) -> Duration {
+        ticks_to_duration(
+            self.base_count().wrapping_sub(start),
+            self.timer.frequency(),
+        )
+    }
+
+    /// Sets the compare value to `duration` after the current count. The control register is not
+    /// changed.
+    ///
+    /// A deadline beyond `u64::MAX` saturates to the farthest representable compare value instead
+    /// of wrapping around. Depending on the frequency such a deadline may be decades or centuries
+    /// away, which makes it an "effectively never" deadline. Use [`Timer::try_set_deadline`] to
+    /// detect this case instead.
+    pub fn set_deadline(&mut self, duration: Duration) {
+        self.set_deadline_from(duration, self.base_count());
+    }
+
+    /// Sets the compare value to `duration` after `base_count`. The control register is not
+    /// changed.
+    ///
+    /// This gives explicit control over the count the deadline is relative to, e.g. to arm a
+    /// virtual timer relative to a physical count sample. The compare value saturates at
+    /// `u64::MAX`.
+    pub fn set_deadline_from(&mut self, duration: Duration, base_count: u64) {
+        let ticks = self.deadline_ticks(duration);
+        self.timer
+            .set_compare_value(base_count.saturating_add(ticks));
+    }
+
+    /// Sets the compare value to `duration` after the current count. The control register is not
+    /// changed.
+    ///
+    /// Returns [`TimerError::DeadlineOverflow`] and leaves the timer untouched if the deadline is
+    /// beyond `u64::MAX`, instead of saturating like [`Timer::set_deadline`].
+    pub fn try_set_deadline(&mut self, duration: Duration) -> Result<(), TimerError> {
+        let compare_value = self.checked_deadline_after(duration)?;
+        self.timer.set_compare_value(compare_value);
+
+        Ok(())
+    }
+
+    /// Sets the compare value to `ticks` after the current count by writing the down-counter
+    /// register. The hardware calculates the compare value from the current count, so this arms a
+    /// relative deadline without reading the count. The control register is not changed.
+    ///
+    /// The timer value is a signed 32-bit value, so `ticks` must be less than `1 << 31`.
+    pub fn set_timer_value(&mut self, ticks: u32) {
+        debug_assert!(ticks <= i32::MAX as u32);
+        self.timer.set_timer_value(ticks);
+    }
+
+    /// Gets the down-counter value, i.e. the compare value minus the current count truncated to 32
+    /// bits.
+    pub fn timer_value(&self) -> u32 {
+        self.timer.timer_value()
+    }
+
+    /// Arms the timer as a software watchdog, which fires after `timeout` unless [`Timer::pet`] is
+    /// called in time.
+    ///
+    /// The timer interrupt is unmasked. The caller is responsible for routing the interrupt to a
+    /// handler which resets the system.
+    pub fn arm_watchdog(&mut self, timeout: Duration) {
+        let ticks = self.deadline_ticks(timeout);
+        self.watchdog_timeout = Some(ticks);
+        self.arm_at(self.base_count().saturating_add(ticks));
+    }
+
+    /// Restarts the watchdog timeout from the current count. Does nothing if the watchdog has not
+    /// been armed.
+    pub fn pet(&mut self) {
+        if let Some(ticks) = self.watchdog_timeout {
+            self.arm_at(self.base_count().saturating_add(ticks));
+        }
+    }
+
+    /// Arms the timer to fire after `duration` with the interrupt unmasked, and returns a token
+    /// which identifies this deadline.
+    pub fn arm_after_token(&mut self, duration: Duration) -> TimerToken {
+        let compare_value = self.deadline_after(duration);
+        self.arm_at(compare_value);
+
+        TimerToken { compare_value }
+    }
+
+    /// Disables the timer if it is still armed with the deadline identified by the token.
+    ///
+    /// Returns `false` and leaves the timer untouched if it has been disabled or re-armed with a
+    /// different deadline since the token was issued.
+    pub fn cancel_token(&mut self, token: TimerToken) -> bool {
+        let control = self.timer.control();
+
+        if control.contains(TimerControl::ENABLE)
+            && self.timer.compare_value() == token.compare_value
+        {
+            self.disable();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the minimum number of ticks between the current count and a deadline which can be
+    /// armed safely, i.e. without the count passing the deadline while the timer is programmed.
+    ///
+    /// This is a conservative constant of one microsecond, but at least one tick.
+    pub fn minimum_safe_ticks(&self) -> u64 {
+        self.deadline_ticks(Self::MINIMUM_SAFE_DELAY).max(1)
+    }
+
+    /// Arms the timer to fire after `duration` with the interrupt unmasked.
+    ///
+    /// Returns [`TimerError::DeadlineTooClose`] and leaves the timer untouched if `duration` is
+    /// shorter than [`Timer::minimum_safe_ticks`], or [`TimerError::DeadlineOverflow`] if the
+    /// deadline is beyond `u64::MAX`.
+    pub fn arm_after_checked(&mut self, duration: Duration) -> Result<(), TimerError> {
+        if self.deadline_ticks(duration) < self.minimum_safe_ticks() {
+            return Err(TimerError::DeadlineTooClose);
+        }
+
+        let compare_value = self.checked_deadline_after(duration)?;
+        self.arm_at(compare_value);
+
+        Ok(())
+    }
+
+    /// Gets the longest delay after `now` which can be represented as a compare value, i.e. the
+    /// time until the count reaches `u64::MAX`.
+    ///
+    /// Panics if the frequency of the timer is zero.
+    pub fn max_delay(&self, now: u64) -> Duration {
+        ticks_to_duration(u64::MAX - now, self.timer.frequency())
+    }
+
+    /// Arms the timer to fire `duration` after `now` with the interrupt unmasked. A duration longer
+    /// than [`Timer::max_delay`] is clamped to the largest representable compare value, so the
+    /// deadline never wraps around to a count in the near future.
+    pub fn arm_after_clamped(&mut self, duration: Duration, now: u64) {
+        let ticks = self.deadline_ticks(duration);
+        self.arm_at(now.saturating_add(ticks));
+    }
+
+    /// Checks whether the programmed compare value is still ahead of `now` on a counter which is
+    /// `counter_width` bits wide.
+    ///
+    /// The comparison is modular: the compare value is in the future if it is less than half of the
+    /// counter range ahead of `now`. This gives the correct result even if the count wrapped around
+    /// since the compare value was programmed.
+    ///
+    /// Panics if `counter_width` is not in the range 1..=64.
+    pub fn is_in_future(&self, now: u64, counter_width: u8) -> bool {
+        assert!((1..=64).contains(&counter_width));
+
+        let mask = u64::MAX >> (64 - counter_width);
+        let delta = self.timer.compare_value().wrapping_sub(now) & mask;
+
+        delta != 0 && delta <= mask >> 1
+    }
+
+    /// Captures the compare value, the down-counter value and the control register of the timer.
+    pub fn snapshot(&self) -> TimerRegsSnapshot {
+        TimerRegsSnapshot {
+            compare_value: self.timer.compare_value(),
+            timer_value: self.timer.timer_value(),
+            control: self.timer.control(),
+        }
+    }
+
+    /// Restores the timer registers from a snapshot.
+    ///
+    /// The timer is disabled while the compare value is written and the control register is
+    /// restored afterwards, so the timer cannot fire on a partially restored state. The
+    /// down-counter value is not written, as it is derived from the compare value and writing it
+    /// would reprogram the compare value relative to the current count.
+    pub fn restore(&mut self, snapshot: &TimerRegsSnapshot) {
+        self.timer.set_control(TimerControl::IMASK);
+        self.timer.set_compare_value(snapshot.compare_value);
+        self.timer
+            .set_control(snapshot.control.difference(TimerControl::ISTATUS));
+    }
+
+    /// Checks whether the deadline has passed, i.e. the timer is enabled and its condition is met.
+    /// This allows polling timers without blocking. The interrupt mask is not taken into account.
+    pub fn is_expired(&self) -> bool {
+        self.timer.control().is_pending()
+    }
+
+    /// Arms the timer to expire after `duration`, with the interrupt masked. The expiry can be
+    /// checked later by [`Timer::is_expired`], which allows multiplexing several timers in a single
+    /// loop without blocking.
+    pub fn arm(&mut self, duration: Duration) {
+        self.set_deadline(duration);
+        self.timer.set_control(TimerControl::enabled_polling());
+    }
+
+    /// Checks whether the timer will signal an interrupt within `horizon` after `now`.
+    ///
+    /// A deadline which has already passed fires immediately. Returns `false` if the timer is
+    /// disabled or its interrupt is masked, as it cannot cut a low-power state short then.
+    pub fn fires_within(&self, horizon: Duration, now: u64) -> bool {
+        let control = self.timer.control();
+        if !control.contains(TimerControl::ENABLE) || control.contains(TimerControl::IMASK) {
+            return false;
+        }
+
+        let ticks = self.deadline_ticks(horizon);
+        self.timer.compare_value().saturating_sub(now) <= ticks
+    }
+
+    /// Gets the time left until the programmed compare value is reached, or `None` if the deadline
+    /// has already passed. The current count is read from the count source of the timer, see
+    /// [`Timer::base_count`].
+    pub fn remaining(&self) -> Option<Duration> {
+        let ticks = self.timer.compare_value().checked_sub(self.base_count())?;
+
+        (ticks != 0).then(|| ticks_to_duration(ticks, self.timer.frequency()))
+    }
+
+    /// Converts `duration` to ticks of the timer for deadline calculations. The result is rounded
+    /// up, so waits and deadlines never expire before `duration` has passed.
+    ///
+    /// A zero frequency, e.g. because CNTFRQ was never programmed, turns every duration into zero
+    /// ticks, so waits and deadlines would complete immediately. This is caught by a debug
+    /// assertion.
+    fn deadline_ticks(&self, duration: Duration) -> u64 {
+        let frequency = self.timer.frequency();
+        debug_assert_ne!(
+            0, frequency,
+            "Timer frequency is zero, CNTFRQ is not programmed"
+        );
+
+        duration_to_ticks_ceil(duration, frequency)
+    }
+
+    /// Calculates the compare value of a deadline `duration` after the current count.
+    fn deadline_after(&self, duration: Duration) -> u64 {
+        let ticks = self.deadline_ticks(duration);
+        self.base_count().saturating_add(ticks)
+    }
+
+    /// Calculates the compare value of a deadline `duration` after the current count, or returns
+    /// [`TimerError::DeadlineOverflow`] if it is not representable.
+    fn checked_deadline_after(&self, duration: Duration) -> Result<u64, TimerError> {
+        let ticks = self.deadline_ticks(duration);
+        self.base_count()
+            .checked_add(ticks)
+            .ok_or(TimerError::DeadlineOverflow)
+    }
+
+    /// Gets the absolute compare value of the timer.
+    pub fn compare(&self) -> u64 {
+        self.timer.compare_value()
+    }
+
+    /// Sets the absolute compare value of the timer, leaving the control register unchanged. Timers
+    /// sharing a count source fire at the same tick when set to the same compare value.
+    pub fn set_compare(&mut self, count: u64) {
+        self.timer.set_compare_value(count);
+    }
+
+    /// Arms the timer to fire when the count reaches `compare_value`, with the interrupt unmasked.
+    pub fn arm_at(&mut self, compare_value: u64) {
+        self.timer.set_compare_value(compare_value);
+        self.timer
+            .set_control(TimerControl::enabled_with_interrupt());
+    }
+}
+
+impl<T: TimerInterface> CounterFrequency for Timer<T> {
+    fn frequency(&self) -> u32 {
+        self.timer.frequency()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    extern crate std;
     use core::cell::Cell;
+    use std::string::ToString;
 
     struct MockTimer<'a> {
         frequency: u32,
         timer_values: &'a [u32],
         value_index: Cell<usize>,
+        count: u64,
+        compare_value: u64,
+        control: TimerControl,
     }
 
     impl<'a> MockTimer<'a> {
@@ -76,6 +966,9 @@ mod tests {
                 frequency,
                 timer_values,
                 value_index: Cell::new(0),
+                count: 0,
+                compare_value: 0,
+                control: TimerControl::empty(),
             }
         }
     }
@@ -103,6 +996,30 @@ mod tests {
 
             self.timer_values[index]
         }
+
+        fn set_timer_value(&mut self, value: u32) {
+            self.compare_value = self.count.wrapping_add_signed(i64::from(value as i32));
+        }
+
+        fn count(&self) -> u64 {
+            self.count
+        }
+
+        fn compare_value(&self) -> u64 {
+            self.compare_value
+        }
+
+        fn set_compare_value(&mut self, value: u64) {
+            self.compare_value = value;
+        }
+
+        fn control(&self) -> TimerControl {
+            self.control
+        }
+
+        fn set_control(&mut self, control: TimerControl) {
+            self.control = control;
+        }
     }
 
     #[test]
@@ -113,11 +1030,581 @@ mod tests {
         timer.wait(Duration::from_secs(5));
     }
 
+    #[test]
+    fn wait_never_short() {
+        // 2.4 ms is 2.4 ticks, so the wait must only end after the third tick.
+        let mock = MockTimer::new(1000, &[10, 8, 7]);
+
+        let timer = Timer::new(mock);
+        timer.wait(Duration::from_micros(2400));
+    }
+
     #[test]
     fn wait_overflow() {
-        let mock = MockTimer::new(1000, &[2000, 1000, 2001]);
+        let mock = MockTimer::new(1000, &[4_000_000_000, 0, 3_000_000_000]);
 
         let timer = Timer::new(mock);
-        timer.wait(Duration::from_secs(u64::MAX));
+        timer.wait(Duration::from_secs(5_000_000));
+        assert_eq!(3, timer.timer.value_index.get());
+    }
+
+    #[test]
+    fn timer_error_display() {
+        assert_eq!(
+            "deadline overflows the compare value",
+            TimerError::DeadlineOverflow.to_string()
+        );
+        assert_eq!(
+            "timer frame index out of range",
+            TimerError::FrameOutOfRange.to_string()
+        );
+    }
+
+    #[test]
+    fn ticks_to_duration() {
+        assert_eq!(Duration::ZERO, super::ticks_to_duration(0, 1000));
+        assert_eq!(
+            Duration::from_millis(1500),
+            super::ticks_to_duration(1500, 1000)
+        );
+        assert_eq!(
+            Duration::from_nanos(20),
+            super::ticks_to_duration(1, 50_000_000)
+        );
+        assert_eq!(
+            Duration::new(u64::MAX, 0),
+            super::ticks_to_duration(u64::MAX, 1)
+        );
+    }
+
+    #[test]
+    fn watchdog() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.pet();
+        assert_eq!(0, timer.timer.compare_value);
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+
+        timer.timer.count = 5000;
+        timer.arm_watchdog(Duration::from_secs(2));
+        assert_eq!(7000, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        timer.timer.count = 6500;
+        timer.pet();
+        assert_eq!(8500, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+    }
+
+    #[test]
+    fn ticks_to_micros() {
+        assert_eq!(0, super::ticks_to_micros(0, 50_000_000));
+        assert_eq!(0, super::ticks_to_micros(49, 50_000_000));
+        assert_eq!(1, super::ticks_to_micros(50, 50_000_000));
+        assert_eq!(1, super::ticks_to_micros(99, 50_000_000));
+        assert_eq!(1_500_000, super::ticks_to_micros(1500, 1000));
+        assert_eq!(u64::MAX, super::ticks_to_micros(u64::MAX, 1));
+    }
+
+    #[test]
+    fn duration_to_ticks() {
+        let nanos = |nanos| super::duration_to_ticks(Duration::from_nanos(nanos), 50_000_000);
+        assert_eq!(0, nanos(0));
+        assert_eq!(0, nanos(9));
+        assert_eq!(1, nanos(10));
+        assert_eq!(1, nanos(29));
+        assert_eq!(49, nanos(989));
+        assert_eq!(50, nanos(990));
+
+        assert_eq!(
+            1500,
+            super::duration_to_ticks(Duration::from_millis(1500), 1000)
+        );
+        assert_eq!(u64::MAX, super::duration_to_ticks(Duration::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn duration_to_ticks_ceil() {
+        let nanos = |nanos| super::duration_to_ticks_ceil(Duration::from_nanos(nanos), 50_000_000);
+        assert_eq!(0, nanos(0));
+        assert_eq!(1, nanos(1));
+        assert_eq!(1, nanos(20));
+        assert_eq!(2, nanos(21));
+        assert_eq!(50, nanos(981));
+        assert_eq!(50, nanos(1000));
+
+        assert_eq!(
+            u64::MAX,
+            super::duration_to_ticks_ceil(Duration::MAX, u32::MAX)
+        );
+    }
+
+    #[test]
+    fn is_in_future() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+
+        timer.timer.compare_value = 1000;
+        assert!(timer.is_in_future(999, 64));
+        assert!(!timer.is_in_future(1000, 64));
+        assert!(!timer.is_in_future(1001, 64));
+
+        // Compare value programmed after the count wrapped around.
+        timer.timer.compare_value = 10;
+        assert!(timer.is_in_future(u64::MAX - 10, 64));
+        assert!(timer.is_in_future(0xff_fff0, 24));
+        assert!(!timer.is_in_future(0x80_000a, 24));
+        assert!(!timer.is_in_future(20, 24));
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[500]));
+        timer.timer.compare_value = 1500;
+        timer.timer.control = TimerControl::ENABLE | TimerControl::ISTATUS;
+
+        let snapshot = timer.snapshot();
+        assert_eq!(
+            TimerRegsSnapshot {
+                compare_value: 1500,
+                timer_value: 500,
+                control: TimerControl::ENABLE | TimerControl::ISTATUS,
+            },
+            snapshot
+        );
+
+        timer.timer.compare_value = 0;
+        timer.timer.control = TimerControl::IMASK;
+        timer.restore(&snapshot);
+        assert_eq!(1500, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+    }
+
+    #[test]
+    fn cancel_token() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 1000;
+
+        let token = timer.arm_after_token(Duration::from_secs(1));
+        assert_eq!(2000, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+        assert!(timer.cancel_token(token));
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+
+        let stale_token = timer.arm_after_token(Duration::from_secs(1));
+        timer.arm_after_token(Duration::from_secs(2));
+        assert!(!timer.cancel_token(stale_token));
+        assert_eq!(3000, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+    }
+
+    #[test]
+    fn arm_after_clamped() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        assert_eq!(Duration::from_secs(1), timer.max_delay(u64::MAX - 1000));
+
+        timer.arm_after_clamped(Duration::from_secs(1), 5000);
+        assert_eq!(6000, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        timer.arm_after_clamped(Duration::MAX, 5000);
+        assert_eq!(u64::MAX, timer.timer.compare_value);
+
+        timer.arm_after_clamped(Duration::from_secs(2), u64::MAX - 1000);
+        assert_eq!(u64::MAX, timer.timer.compare_value);
+    }
+
+    #[test]
+    fn arm_after_checked() {
+        let mut timer = Timer::new(MockTimer::new(50_000_000, &[]));
+        timer.timer.count = 1000;
+        assert_eq!(50, timer.minimum_safe_ticks());
+
+        assert_eq!(
+            Err(TimerError::DeadlineTooClose),
+            timer.arm_after_checked(Duration::from_nanos(500))
+        );
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+
+        assert_eq!(Ok(()), timer.arm_after_checked(Duration::from_micros(1)));
+        assert_eq!(1050, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        let timer = Timer::new(MockTimer::new(1000, &[]));
+        assert_eq!(1, timer.minimum_safe_ticks());
+    }
+
+    #[test]
+    fn set_deadline() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 1000;
+
+        timer.set_deadline(Duration::from_millis(500));
+        assert_eq!(1500, timer.timer.compare_value);
+
+        // Deadlines used by the delays are rounded up to the next tick, so they never expire early.
+        timer.set_deadline(Duration::from_micros(2400));
+        assert_eq!(1003, timer.timer.compare_value);
+
+        timer.set_deadline_from(Duration::from_millis(500), 5000);
+        assert_eq!(5500, timer.timer.compare_value);
+
+        timer.set_deadline_from(Duration::from_secs(1), u64::MAX - 10);
+        assert_eq!(u64::MAX, timer.timer.compare_value);
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+    }
+
+    #[test]
+    fn try_set_deadline() {
+        let mut timer = Timer::new(MockTimer::new(1_000_000_000, &[]));
+        timer.timer.count = u64::MAX - 1_000_000;
+
+        assert_eq!(Ok(()), timer.try_set_deadline(Duration::from_micros(500)));
+        assert_eq!(u64::MAX - 500_000, timer.timer.compare_value);
+
+        assert_eq!(
+            Err(TimerError::DeadlineOverflow),
+            timer.try_set_deadline(Duration::from_secs(3 * 3600))
+        );
+        assert_eq!(u64::MAX - 500_000, timer.timer.compare_value);
+
+        assert_eq!(
+            Err(TimerError::DeadlineOverflow),
+            timer.arm_after_checked(Duration::from_secs(3 * 3600))
+        );
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+    }
+
+    #[test]
+    fn set_timer_value() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[250]));
+        timer.timer.count = 1000;
+
+        timer.set_timer_value(250);
+        assert_eq!(1250, timer.timer.compare_value);
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+        assert_eq!(250, timer.timer_value());
+    }
+
+    #[test]
+    fn is_expired() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        assert!(!timer.is_expired());
+
+        timer.timer.control = TimerControl::ENABLE;
+        assert!(!timer.is_expired());
+
+        timer.timer.control = TimerControl::ENABLE | TimerControl::IMASK | TimerControl::ISTATUS;
+        assert!(timer.is_expired());
+
+        timer.timer.control = TimerControl::ISTATUS;
+        assert!(!timer.is_expired());
+    }
+
+    #[test]
+    fn count_reached() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 499;
+        assert!(!timer.count_reached(500));
+
+        // Acknowledging the interrupt disables the timer, but the deadline has still passed.
+        timer.timer.count = 500;
+        timer.timer.control = TimerControl::empty();
+        assert!(timer.count_reached(500));
+        assert!(!timer.is_expired());
+
+        timer.timer.count = u64::MAX;
+        assert!(timer.count_reached(u64::MAX));
+    }
+
+    #[test]
+    fn arm_and_poll() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 500;
+        timer.arm(Duration::from_millis(250));
+        assert_eq!(750, timer.timer.compare_value);
+        assert_eq!(
+            TimerControl::ENABLE | TimerControl::IMASK,
+            timer.timer.control
+        );
+        assert!(!timer.is_expired());
+
+        timer.timer.control |= TimerControl::ISTATUS;
+        assert!(timer.is_expired());
+    }
+
+    #[test]
+    fn control_flags() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        assert!(!timer.enabled());
+        assert!(!timer.interrupt_masked());
+        assert!(!timer.condition_met());
+
+        timer.set_interrupt_masked(true);
+        timer.set_enabled(true);
+        assert_eq!(
+            TimerControl::ENABLE | TimerControl::IMASK,
+            timer.timer.control
+        );
+        assert!(timer.enabled());
+        assert!(timer.interrupt_masked());
+
+        timer.timer.control |= TimerControl::ISTATUS;
+        assert!(timer.condition_met());
+
+        timer.set_interrupt_masked(false);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+        timer.set_enabled(false);
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+
+        // A stale ISTATUS of a disabled timer is reported, but the timer has not expired.
+        timer.timer.control = TimerControl::ISTATUS;
+        assert!(timer.condition_met());
+        assert!(!timer.is_expired());
+    }
+
+    #[test]
+    fn set_compare() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.control = TimerControl::IMASK;
+
+        timer.set_compare(0x1234_5678_9abc);
+        assert_eq!(0x1234_5678_9abc, timer.compare());
+        assert_eq!(TimerControl::IMASK, timer.timer.control);
+    }
+
+    #[test]
+    fn set_deadline_saturating() {
+        let mut timer = Timer::new(MockTimer::new(1_000_000_000, &[]));
+        timer.timer.count = u64::MAX - 1000;
+
+        timer.set_deadline(Duration::MAX);
+        assert_eq!(u64::MAX, timer.timer.compare_value);
+    }
+
+    #[test]
+    fn reset() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.arm_periodic_hz(100, 0);
+        timer.timer.control |= TimerControl::IMASK | TimerControl::ISTATUS;
+
+        timer.reset();
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+        assert_eq!(0, timer.timer.compare_value);
+        assert!(timer.period.is_none());
+        assert!(timer.watchdog_timeout.is_none());
+    }
+
+    #[test]
+    fn elapsed_since() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 2500;
+        assert_eq!(Duration::from_millis(2000), timer.elapsed_since(500));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Timer frequency is zero")]
+    fn zero_frequency() {
+        let mut timer = Timer::new(MockTimer::new(0, &[]));
+        timer.set_deadline(Duration::from_millis(1));
+    }
+
+    #[test]
+    fn advance_deadline() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 1500;
+        timer.timer.compare_value = 1000;
+        timer.timer.control = TimerControl::ENABLE;
+
+        timer.advance_deadline(Duration::from_millis(100));
+        assert_eq!(1100, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        assert_eq!(
+            4,
+            timer.advance_deadline_skipping(Duration::from_millis(100))
+        );
+        assert_eq!(1600, timer.timer.compare_value);
+
+        assert_eq!(
+            0,
+            timer.advance_deadline_skipping(Duration::from_millis(100))
+        );
+        assert_eq!(1700, timer.timer.compare_value);
+    }
+
+    #[test]
+    fn advance_deadline_fractional() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.compare_value = 1000;
+
+        // 1.5 ms is 1.5 ticks, the fractional tick is carried over instead of accumulating error.
+        for expected in [1001, 1003, 1004, 1006] {
+            timer.advance_deadline(Duration::from_micros(1500));
+            assert_eq!(expected, timer.timer.compare_value);
+        }
+
+        timer.timer.count = 1020;
+        assert_eq!(
+            9,
+            timer.advance_deadline_skipping(Duration::from_micros(1500))
+        );
+        assert_eq!(1021, timer.timer.compare_value);
+
+        timer.advance_deadline(Duration::from_micros(1500));
+        assert_eq!(1022, timer.timer.compare_value);
+    }
+
+    #[test]
+    fn tick_conversion() {
+        let timer = Timer::new(MockTimer::new(50_000_000, &[]));
+        assert_eq!(Duration::from_nanos(20), timer.ticks_to_duration(1));
+        assert_eq!(50_000_000, timer.duration_to_ticks(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn remaining() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 1000;
+        timer.timer.compare_value = 1250;
+        assert_eq!(Some(Duration::from_millis(250)), timer.remaining());
+
+        timer.timer.count = 1250;
+        assert_eq!(None, timer.remaining());
+
+        timer.timer.count = 2000;
+        assert_eq!(None, timer.remaining());
+    }
+
+    #[test]
+    fn time_domain_map() {
+        let map = TimeDomainMap {
+            local_anchor_count: 1_000_000,
+            foreign_anchor: 5_000,
+            rate_ratio: (5, 2),
+        };
+
+        assert_eq!(1_000_000, map.to_local_count(5_000));
+        assert_eq!(1_002_500, map.to_local_count(6_000));
+        assert_eq!(997_500, map.to_local_count(4_000));
+        assert_eq!(987_500, map.to_local_count(0));
+
+        let map = TimeDomainMap {
+            local_anchor_count: u64::MAX - 1,
+            foreign_anchor: 0,
+            rate_ratio: (u32::MAX, 1),
+        };
+        assert_eq!(u64::MAX, map.to_local_count(u64::MAX));
+
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.arm_at(map.to_local_count(0));
+        assert_eq!(u64::MAX - 1, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+    }
+
+    #[test]
+    fn fires_within() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.compare_value = 2000;
+        assert!(!timer.fires_within(Duration::from_secs(2), 1000));
+
+        timer.timer.control = TimerControl::ENABLE;
+        assert!(timer.fires_within(Duration::from_secs(2), 1000));
+        assert!(timer.fires_within(Duration::from_secs(1), 1000));
+        assert!(!timer.fires_within(Duration::from_millis(999), 1000));
+        assert!(timer.fires_within(Duration::ZERO, 3000));
+
+        timer.timer.control = TimerControl::ENABLE | TimerControl::IMASK;
+        assert!(!timer.fires_within(Duration::from_secs(2), 1000));
+    }
+
+    #[test]
+    fn instant() {
+        let base = Instant::new(u64::MAX - 499, 1000);
+        let now = Instant::new(2500, 1000);
+
+        assert_eq!(Duration::from_secs(3), now.duration_since(base));
+        assert_eq!(3, now.periods_since(base, Duration::from_secs(1)));
+        assert_eq!(2, now.periods_since(base, Duration::from_millis(1500)));
+        assert_eq!(4, now.periods_since(base, Duration::from_micros(749_999)));
+        assert_eq!(0, now.periods_since(base, Duration::MAX));
+        assert_eq!(0, base.periods_since(base, Duration::from_nanos(1)));
+
+        let max_count = 0x00ff_ffff_ffff_ffff;
+        let base = Instant::with_max_count(max_count - 499, 1000, max_count);
+        let now = Instant::with_max_count(2500, 1000, max_count);
+        assert_eq!(Duration::from_secs(3), now.duration_since(base));
+        assert_eq!(3, now.periods_since(base, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn one_shot() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.timer.count = 1000;
+
+        timer.arm_one_shot(Duration::from_millis(100));
+        assert_eq!(1100, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        timer.timer.control |= TimerControl::ISTATUS;
+        timer.handle_one_shot();
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+    }
+
+    #[test]
+    fn periodic_hz() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.rearm();
+        assert_eq!(TimerControl::empty(), timer.timer.control);
+
+        timer.arm_periodic_hz(3, 5000);
+        assert_eq!(5333, timer.timer.compare_value);
+        assert_eq!(TimerControl::ENABLE, timer.timer.control);
+
+        timer.rearm();
+        assert_eq!(5666, timer.timer.compare_value);
+        timer.rearm();
+        assert_eq!(6000, timer.timer.compare_value);
+
+        for _ in 0..300 {
+            timer.rearm();
+        }
+        assert_eq!(106_000, timer.timer.compare_value);
+    }
+
+    #[test]
+    fn periodic_drift() {
+        let mut timer = Timer::new(MockTimer::new(3, &[]));
+        assert_eq!(0, timer.periodic_drift());
+
+        // One period is 1.5 ticks.
+        timer.arm_periodic(Duration::from_millis(500), 0);
+        assert_eq!(1, timer.timer.compare_value);
+        assert_eq!(-166_666_666, timer.periodic_drift());
+
+        timer.rearm();
+        assert_eq!(3, timer.timer.compare_value);
+        assert_eq!(0, timer.periodic_drift());
+
+        timer.rearm();
+        timer.rearm();
+        assert_eq!(6, timer.timer.compare_value);
+        assert_eq!(0, timer.periodic_drift());
+    }
+
+    #[test]
+    fn assert_deadline_advanced() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.arm_periodic_hz(10, 0);
+        timer.rearm();
+        timer.assert_deadline_advanced(100);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Timer deadline not advanced")]
+    fn assert_deadline_not_advanced() {
+        let mut timer = Timer::new(MockTimer::new(1000, &[]));
+        timer.arm_periodic_hz(10, 0);
+        timer.assert_deadline_advanced(100);
     }
 }
@@ -5,48 +5,75 @@
 //!
 //! See I5.6 Generic Timer memory-mapped registers overview.
 
-use crate::{Timer, TimerInterface};
+use crate::{
+    CounterFrequency, Instant, Timer, TimerError, TimerInterface, TimerRegsSnapshot,
+    duration_to_ticks, duration_to_ticks_ceil, ticks_to_duration,
+};
 use bitflags::bitflags;
+use core::{fmt, hint::spin_loop, ptr::NonNull, time::Duration};
 use safe_mmio::{
-    UniqueMmioPointer, field, field_shared,
+    SharedMmioPointer, UniqueMmioPointer, field, field_shared,
     fields::{ReadPure, ReadPureWrite},
+    split_fields,
 };
-use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
+
+/// Number of entries in the Frequency modes table.
+const FREQUENCY_MODE_COUNT: usize = 40;
+
+/// Maximal number of timer frames.
+const FRAME_COUNT: usize = 8;
+
+/// Number of Counter ID registers at the end of each frame.
+const COUNTER_ID_COUNT: usize = 12;
 
 /// Counter Control Register
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CntCr(u32);
 
 /// Counter Status Register
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CntSr(u32);
 
 /// Counter Identification Register.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CntId(u32);
 
 /// Counter-timer Access Control Register.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CntAcr(u32);
 
+/// Counter-timer Non-secure Access Register.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CntNsar(u32);
+
 /// Timer feature bits, defined at I5.7.16 CNTTIDR, Counter-timer Timer ID Register description.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Features(u8);
 
 /// Counter-timer EL0 Access Control Register.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CntEl0Acr(u32);
 
 /// Common control register of the physical and virtual timers. Defined at I5.7.10 CNTP_CTL,
 /// Counter-timer Physical Timer Control and at CNTV_CTL, Counter-timer Virtual Timer Control.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimerControl(u32);
 
 bitflags! {
@@ -79,6 +106,25 @@ bitflags! {
         const RPCT = 1 << 0;
     }
 
+    impl CntNsar: u32 {
+        /// Non-secure access to frame 7.
+        const NS7 = 1 << 7;
+        /// Non-secure access to frame 6.
+        const NS6 = 1 << 6;
+        /// Non-secure access to frame 5.
+        const NS5 = 1 << 5;
+        /// Non-secure access to frame 4.
+        const NS4 = 1 << 4;
+        /// Non-secure access to frame 3.
+        const NS3 = 1 << 3;
+        /// Non-secure access to frame 2.
+        const NS2 = 1 << 2;
+        /// Non-secure access to frame 1.
+        const NS1 = 1 << 1;
+        /// Non-secure access to frame 0.
+        const NS0 = 1 << 0;
+    }
+
     impl Features: u8 {
         /// Frame<n> has a second view, CNTEL0Base<n>.
         const CNTEL0BASE = 1 << 2;
@@ -109,10 +155,109 @@ bitflags! {
     }
 }
 
+/// Implements `Display` for bitflags types, printing the names of the set flags separated by `|`,
+/// followed by the value of the remaining bits which don't belong to a named flag. An empty value
+/// is printed as `0x0`, like by `impl_defmt_format`.
+macro_rules! impl_display {
+    ($($flags:ty),* $(,)?) => {
+        $(
+            impl fmt::Display for $flags {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    if self.is_empty() {
+                        write!(f, "{:#x}", 0)
+                    } else {
+                        bitflags::parser::to_writer(self, f)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(
+    CntCr,
+    CntSr,
+    CntAcr,
+    CntNsar,
+    Features,
+    CntEl0Acr,
+    TimerControl
+);
+
+/// Implements `defmt::Format` for bitflags types, printing the names of the set flags followed by
+/// the value of the remaining bits which don't belong to a named flag. An empty value is printed as
+/// `0x0`, like by `impl_display`.
+#[cfg(feature = "defmt")]
+macro_rules! impl_defmt_format {
+    ($($flags:ty),* $(,)?) => {
+        $(
+            impl defmt::Format for $flags {
+                fn format(&self, f: defmt::Formatter) {
+                    let mut names = self.iter_names();
+                    let mut first = true;
+
+                    for (name, _) in &mut names {
+                        if !first {
+                            defmt::write!(f, " | ");
+                        }
+                        defmt::write!(f, "{=str}", name);
+                        first = false;
+                    }
+
+                    let remaining = names.remaining().bits();
+                    if remaining != 0 || first {
+                        if !first {
+                            defmt::write!(f, " | ");
+                        }
+                        defmt::write!(f, "{:#x}", remaining);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "defmt")]
+impl_defmt_format!(
+    CntCr,
+    CntSr,
+    CntAcr,
+    CntNsar,
+    Features,
+    CntEl0Acr,
+    TimerControl
+);
+
+impl TimerControl {
+    /// Returns the control value of an enabled timer with its interrupt unmasked, which signals an
+    /// interrupt while the timer condition is met.
+    pub const fn enabled_with_interrupt() -> Self {
+        Self::ENABLE
+    }
+
+    /// Returns the control value of an enabled timer with its interrupt masked, for polling the
+    /// timer condition through ISTATUS. Masking only stops the interrupt from being signalled, the
+    /// timer condition is still evaluated.
+    pub const fn enabled_polling() -> Self {
+        Self::ENABLE.union(Self::IMASK)
+    }
+
+    /// Checks whether the timer is enabled and its condition is met. ISTATUS is only valid while
+    /// the timer is enabled. The interrupt mask is not taken into account.
+    pub fn is_pending(self) -> bool {
+        self.contains(Self::ENABLE | Self::ISTATUS)
+    }
+}
+
 impl CntCr {
     const FCREQ_MASK: u32 = 0x0000_03ff;
     const FCREQ_SHIFT: u32 = 8;
 
+    /// Read FCREQ field of the register.
+    pub fn fcreq(&self) -> usize {
+        ((self.0 >> Self::FCREQ_SHIFT) & Self::FCREQ_MASK) as usize
+    }
+
     /// Write FCREQ field of the register.
     pub fn set_fcreq(&mut self, index: usize) {
         let mut value = self.0 & !(Self::FCREQ_MASK << Self::FCREQ_SHIFT);
@@ -141,6 +286,351 @@ impl CntId {
     }
 }
 
+/// Values of the Counter ID registers at offset 0xfd0 of each frame.
+///
+/// The registers follow the CoreSight identification scheme: PIDR4-7 at 0xfd0, PIDR0-3 at 0xfe0 and
+/// CIDR0-3 at 0xff0, each register holding one byte of the identification values.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CounterId([u32; COUNTER_ID_COUNT]);
+
+impl CounterId {
+    const PREAMBLE_MASK: u32 = 0xffff_0fff;
+    const PREAMBLE: u32 = 0xb105_000d;
+    const DES_0_1_SHIFT: u32 = 12;
+    const JEDEC_USED: u64 = 1 << 19;
+    const DES_2_SHIFT: u32 = 32;
+
+    /// Creates new instance from the register values, in address order.
+    pub const fn from_registers(registers: [u32; COUNTER_ID_COUNT]) -> Self {
+        Self(registers)
+    }
+
+    /// Returns the register values, in address order.
+    pub const fn registers(&self) -> [u32; COUNTER_ID_COUNT] {
+        self.0
+    }
+
+    /// Returns the 64-bit peripheral ID assembled from PIDR0-7.
+    pub fn peripheral_id(&self) -> u64 {
+        let (pidr4_7, pidr0_3) = self.0[..8].split_at(4);
+
+        pidr0_3
+            .iter()
+            .chain(pidr4_7)
+            .enumerate()
+            .fold(0, |id, (index, pidr)| {
+                id | u64::from(pidr & 0xff) << (index * 8)
+            })
+    }
+
+    /// Returns the 32-bit component ID assembled from CIDR0-3.
+    pub fn component_id(&self) -> u32 {
+        self.0[8..]
+            .iter()
+            .enumerate()
+            .fold(0, |id, (index, cidr)| id | (cidr & 0xff) << (index * 8))
+    }
+
+    /// Returns the JEP106 code of the manufacturer from PIDR1, PIDR2 and PIDR4, or `None` if PIDR2
+    /// indicates that the identity code is not a JEP106 code.
+    pub fn manufacturer(&self) -> Option<Jep106Id> {
+        let peripheral_id = self.peripheral_id();
+
+        if peripheral_id & Self::JEDEC_USED == 0 {
+            return None;
+        }
+
+        Some(Jep106Id {
+            continuation: ((peripheral_id >> Self::DES_2_SHIFT) & 0xf) as u8,
+            identity: ((peripheral_id >> Self::DES_0_1_SHIFT) & 0x7f) as u8,
+        })
+    }
+
+    /// Checks whether the component ID contains the preamble of a valid component. The component
+    /// class is ignored.
+    pub fn is_valid(&self) -> bool {
+        self.component_id() & Self::PREAMBLE_MASK == Self::PREAMBLE
+    }
+
+    /// Checks the preamble of the component ID like [`CounterId::is_valid`], returning
+    /// [`TimerError::ComponentMismatch`] if it doesn't match.
+    pub fn verify(&self) -> Result<(), TimerError> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(TimerError::ComponentMismatch)
+        }
+    }
+
+    /// Reads the Counter ID registers of a frame.
+    fn read(registers: SharedMmioPointer<'_, [ReadPure<u32>; COUNTER_ID_COUNT]>) -> Self {
+        Self(core::array::from_fn(|index| {
+            registers.get(index).unwrap().read()
+        }))
+    }
+}
+
+/// JEP106 manufacturer identification code of a component.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Jep106Id {
+    /// Number of continuation codes, i.e. the bank of the identity code.
+    pub continuation: u8,
+    /// Identity code within the bank, without the parity bit.
+    pub identity: u8,
+}
+
+impl Jep106Id {
+    /// JEP106 code of Arm Limited.
+    pub const ARM: Self = Self {
+        continuation: 0x4,
+        identity: 0x3b,
+    };
+}
+
+/// Access policy of a timer frame, applied by [`GenericTimerCtl::apply_policy`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FramePolicy {
+    /// No access, the frame is not accessible by Non-secure accesses.
+    NoAccess,
+    /// Read-only access to the physical and virtual counts and the frequency.
+    ReadCounterOnly,
+    /// Read-only access to the physical and virtual counts and the frequency, and read/write access
+    /// to the physical timer.
+    ReadCounterAndTimer,
+    /// Access to the physical and virtual counts, timers, the frequency and the virtual offset.
+    FullPhysicalVirtual,
+}
+
+impl FramePolicy {
+    /// Returns the access controls which implement the policy.
+    pub const fn access_control(self) -> CntAcr {
+        let read_counter = CntAcr::RPCT.union(CntAcr::RVCT).union(CntAcr::RFRQ);
+
+        match self {
+            Self::NoAccess => CntAcr::empty(),
+            Self::ReadCounterOnly => read_counter,
+            Self::ReadCounterAndTimer => read_counter.union(CntAcr::RWPT),
+            Self::FullPhysicalVirtual => CntAcr::all_access(),
+        }
+    }
+}
+
+impl CntAcr {
+    /// Returns the access controls granting full access to a timer frame: read/write access to the
+    /// physical and virtual timers, and read-only access to CNTVOFF, CNTFRQ, CNTVCT and CNTPCT.
+    pub const fn all_access() -> Self {
+        Self::RWPT
+            .union(Self::RWVT)
+            .union(Self::RVOFF)
+            .union(Self::RFRQ)
+            .union(Self::RVCT)
+            .union(Self::RPCT)
+    }
+
+    /// Creates a builder for the access controls of a timer frame, with no access granted.
+    pub const fn builder() -> CntAcrBuilder {
+        CntAcrBuilder(Self::empty())
+    }
+}
+
+/// Builder of [`CntAcr`] values, created by [`CntAcr::builder`]. All methods are `const` so frame
+/// access policies can be defined as constants.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CntAcrBuilder(CntAcr);
+
+impl CntAcrBuilder {
+    /// Grants read/write access to the EL1 Physical Timer registers.
+    pub const fn rw_physical_timer(self) -> Self {
+        Self(self.0.union(CntAcr::RWPT))
+    }
+
+    /// Grants read/write access to the Virtual Timer registers.
+    pub const fn rw_virtual_timer(self) -> Self {
+        Self(self.0.union(CntAcr::RWVT))
+    }
+
+    /// Grants read-only access to CNTVOFF.
+    pub const fn ro_virtual_offset(self) -> Self {
+        Self(self.0.union(CntAcr::RVOFF))
+    }
+
+    /// Grants read-only access to CNTFRQ.
+    pub const fn ro_frequency(self) -> Self {
+        Self(self.0.union(CntAcr::RFRQ))
+    }
+
+    /// Grants read-only access to CNTVCT.
+    pub const fn ro_virtual_count(self) -> Self {
+        Self(self.0.union(CntAcr::RVCT))
+    }
+
+    /// Grants read-only access to CNTPCT.
+    pub const fn ro_physical_count(self) -> Self {
+        Self(self.0.union(CntAcr::RPCT))
+    }
+
+    /// Returns the access controls.
+    pub const fn build(self) -> CntAcr {
+        self.0
+    }
+}
+
+/// Counter scaling factor, the value of the Counter Scale Register (CNTSCR).
+///
+/// The scaling factor is an unsigned fixed-point number with 8 integer and 24 fractional bits. When
+/// scaling is enabled the counter is incremented by this value on each tick. The position of the
+/// binary point is fixed by the architecture, it is not implementation defined.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Scale(u32);
+
+impl Scale {
+    const FRACTION_BITS: u32 = 24;
+
+    /// Scaling factor of 1.0, the counter is incremented by one on each tick.
+    pub const ONE: Self = Self(1 << Self::FRACTION_BITS);
+
+    /// Creates scaling factor from the raw CNTSCR value.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw CNTSCR value.
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the scaling factor as a floating point number.
+    pub fn as_f64(self) -> f64 {
+        f64::from(self.0) / f64::from(Self::ONE.0)
+    }
+
+    /// Returns the scaling factor as a `(numerator, denominator)` pair, for exact integer
+    /// arithmetic. The denominator is always `1 << 24`.
+    pub const fn ratio(self) -> (u32, u32) {
+        (self.0, Self::ONE.0)
+    }
+
+    /// Multiplies a frequency in Hz by the scaling factor, truncating the fractional part and
+    /// saturating at `u32::MAX`.
+    pub const fn scale_frequency(self, frequency: u32) -> u32 {
+        let scaled = (frequency as u64 * self.0 as u64) >> Self::FRACTION_BITS;
+
+        if scaled > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            scaled as u32
+        }
+    }
+}
+
+/// Registers which EL0 may access through the CNTEL0Base second view of a frame, as controlled by
+/// CNTEL0ACR of the corresponding CNTBase frame.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct El0Access {
+    /// CNTPCT is accessible.
+    pub physical_counter: bool,
+    /// CNTVCT is accessible.
+    pub virtual_counter: bool,
+    /// CNTFRQ is accessible, which is the case if either counter is accessible.
+    pub frequency: bool,
+    /// CNTP_CVAL, CNTP_TVAL and CNTP_CTL are accessible.
+    pub physical_timer: bool,
+    /// CNTV_CVAL, CNTV_TVAL and CNTV_CTL are accessible.
+    pub virtual_timer: bool,
+}
+
+impl From<CntEl0Acr> for El0Access {
+    fn from(cntel0acr: CntEl0Acr) -> Self {
+        let physical_counter = cntel0acr.contains(CntEl0Acr::EL0PCTEN);
+        let virtual_counter = cntel0acr.contains(CntEl0Acr::EL0VCTEN);
+
+        Self {
+            physical_counter,
+            virtual_counter,
+            frequency: physical_counter || virtual_counter,
+            physical_timer: cntel0acr.contains(CntEl0Acr::EL0PTEN),
+            virtual_timer: cntel0acr.contains(CntEl0Acr::EL0VTEN),
+        }
+    }
+}
+
+/// Scaling configuration and frequency mode selection of the counter, captured by
+/// [`GenericTimerControl::scaling_snapshot`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ScalingState {
+    /// Scaling factor.
+    pub scale: Scale,
+    /// Whether scaling is enabled.
+    pub scaling_enabled: bool,
+    /// Index of the selected frequency mode.
+    pub frequency_index: usize,
+}
+
+/// Initial configuration of the system counter, consumed by [`GenericTimerControl::initialize`].
+///
+/// By default the frequency modes table is left untouched, mode 0 is selected, scaling is disabled,
+/// the count value is not changed and the counter is enabled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitConfig<'a> {
+    frequency_table: Option<&'a [u32]>,
+    mode: usize,
+    initial_count: Option<u64>,
+    scaling: Option<Scale>,
+    enable: bool,
+}
+
+impl<'a> InitConfig<'a> {
+    /// Creates new instance with the default configuration.
+    pub const fn new() -> Self {
+        Self {
+            frequency_table: None,
+            mode: 0,
+            initial_count: None,
+            scaling: None,
+            enable: true,
+        }
+    }
+
+    /// Sets the frequencies in Hz to program into the Frequency modes table. The table is
+    /// terminated by a zero entry if it is shorter than the 40 available entries.
+    ///
+    /// The first zero entry ends the table, so any following entries must be zero as well.
+    pub const fn frequency_table(mut self, table: &'a [u32]) -> Self {
+        self.frequency_table = Some(table);
+        self
+    }
+
+    /// Selects the entry of the Frequency modes table to use.
+    pub const fn select_mode(mut self, index: usize) -> Self {
+        self.mode = index;
+        self
+    }
+
+    /// Sets the count value to load before enabling the counter.
+    pub const fn initial_count(mut self, count: u64) -> Self {
+        self.initial_count = Some(count);
+        self
+    }
+
+    /// Enables counter scaling with the given scaling factor.
+    pub const fn scaling(mut self, scale: Scale) -> Self {
+        self.scaling = Some(scale);
+        self
+    }
+
+    /// Sets whether the counter is enabled at the end of the initialization.
+    pub const fn enable(mut self, enable: bool) -> Self {
+        self.enable = enable;
+        self
+    }
+}
+
+impl Default for InitConfig<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Table I2-1 CNTControlBase memory map
 #[derive(Clone, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
 #[repr(C, align(4))]
@@ -157,12 +647,12 @@ pub struct CntControlBase {
     /// 0x01c Counter ID register
     cntid: ReadPure<CntId>,
     /// 0x020 Counter Frequency IDs
-    cntfid: [ReadPureWrite<u32>; 40],
+    cntfid: [ReadPureWrite<u32>; FREQUENCY_MODE_COUNT],
     /// 0x0c0 Implementation defined
     impdef_0c0: [u32; 16],
     reserved_100: [u32; 948],
     /// 0xfd0 Counter ID registers
-    counter_id: [ReadPure<u32>; 12],
+    counter_id: [ReadPure<u32>; COUNTER_ID_COUNT],
 }
 
 /// Table I2-2 CNTReadBase memory map
@@ -173,7 +663,7 @@ pub struct CntReadBase {
     cntcv: ReadPure<u64>,
     reserved_8: [u32; 1010],
     /// 0xfd0 Counter ID registers
-    counter_id: [ReadPure<u32>; 12],
+    counter_id: [ReadPure<u32>; COUNTER_ID_COUNT],
 }
 
 /// Table I2-3 CNTCTLBase memory map
@@ -183,22 +673,22 @@ pub struct CntCtlBase {
     /// 0x000 Counter-timer Frequency
     cntfrq: ReadPureWrite<u32>,
     /// 0x004 Counter-timer Non-secure Access Register
-    cntnsar: ReadPureWrite<u32>,
+    cntnsar: ReadPureWrite<CntNsar>,
     /// 0x008 Counter-timer Timer ID Register
     cnttidr: ReadPure<u32>,
     reserved_00c: [u32; 13],
     /// 0x040 Counter-timer Access Control Registers
-    cntacr: [ReadPureWrite<CntAcr>; 8],
+    cntacr: [ReadPureWrite<CntAcr>; FRAME_COUNT],
     reserved_060: [u32; 8],
     /// 0x080 Counter-timer Virtual Offsets
-    cntvoff: [ReadPureWrite<u64>; 8],
+    cntvoff: [ReadPureWrite<u64>; FRAME_COUNT],
     reserved_0c0: [u32; 16],
     /// 0x100 Implementation defined
     impdef_100: [u32; 448],
     reserved_800: [u32; 496],
     impdef_fc0: [u32; 4],
     /// 0xfd0 Counter ID registers
-    counter_id: [ReadPure<u32>; 12],
+    counter_id: [ReadPure<u32>; COUNTER_ID_COUNT],
 }
 
 /// Repeated subset of register that describe a physical or virtual timer in the CntBase or
@@ -234,7 +724,154 @@ pub struct CntBase {
     cntv: TimerRegs,
     reserved: [u32; 996],
     /// 0xfd0 Counter ID registers
-    counter_id: [ReadPure<u32>; 12],
+    counter_id: [ReadPure<u32>; COUNTER_ID_COUNT],
+}
+
+impl CntBase {
+    /// Creates an in-memory frame with the register values of `capture`, e.g. to replay a capture
+    /// of a hardware frame in a host-side test.
+    pub fn from_capture(capture: &FrameCapture) -> Self {
+        let mut regs = Self::new_zeroed();
+        regs.cntpct.0 = capture.physical_count;
+        regs.cntvct.0 = capture.virtual_count;
+        regs.cntfrq.0 = capture.frequency;
+        regs.cntel0acr.0 = capture.el0_access;
+        regs.cntvoff.0 = capture.virtual_offset;
+        regs.cntp = TimerRegs::from_snapshot(&capture.physical_timer);
+        regs.cntv = TimerRegs::from_snapshot(&capture.virtual_timer);
+        regs
+    }
+}
+
+impl TimerRegs {
+    fn from_snapshot(snapshot: &TimerRegsSnapshot) -> Self {
+        Self {
+            cval: ReadPureWrite(snapshot.compare_value),
+            tval: ReadPureWrite(snapshot.timer_value),
+            ctl: ReadPureWrite(snapshot.control),
+        }
+    }
+
+    fn snapshot(regs: SharedMmioPointer<Self>) -> TimerRegsSnapshot {
+        TimerRegsSnapshot {
+            compare_value: field_shared!(regs, cval).read(),
+            timer_value: field_shared!(regs, tval).read(),
+            control: field_shared!(regs, ctl).read(),
+        }
+    }
+}
+
+/// Readable register state of a CNTControlBase frame, captured by [`GenericTimerControl::capture`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlCapture {
+    /// Counter Control Register.
+    pub control: CntCr,
+    /// Counter Status Register.
+    pub status: CntSr,
+    /// Count value.
+    pub count: u64,
+    /// Counter Scale Register.
+    pub scale: u32,
+    /// Counter Identification Register.
+    pub id: CntId,
+    /// Frequency modes table.
+    #[cfg_attr(feature = "serde", serde(with = "serde_array"))]
+    pub frequency_modes: [u32; FREQUENCY_MODE_COUNT],
+}
+
+/// Readable register state of a CNTCTLBase frame, captured by [`GenericTimerCtl::capture`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CtlCapture {
+    /// Frequency in Hz.
+    pub frequency: u32,
+    /// Non-secure access state of the frames.
+    pub non_secure_access: CntNsar,
+    /// Timer ID Register, holding the features of the frames.
+    pub timer_id: u32,
+    /// Access controls of the frames.
+    pub access_control: [CntAcr; FRAME_COUNT],
+    /// Virtual offsets of the frames.
+    pub virtual_offset: [u64; FRAME_COUNT],
+}
+
+/// Serializes arrays of any length as tuples, as `serde` only implements arrays up to 32 elements.
+#[cfg(feature = "serde")]
+mod serde_array {
+    use core::{fmt, marker::PhantomData};
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, SeqAccess, Visitor},
+        ser::SerializeTuple,
+    };
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        array: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in array {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Copy + Default,
+    {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+        where
+            T: Deserialize<'de> + Copy + Default,
+        {
+            type Value = [T; N];
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an array of length {N}")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut array = [T::default(); N];
+                for (index, element) in array.iter_mut().enumerate() {
+                    *element = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(index, &self))?;
+                }
+
+                Ok(array)
+            }
+        }
+
+        deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+    }
+}
+
+/// Readable register state of a CNTBase frame, captured by [`GenericTimerCnt::capture`].
+///
+/// The capture can be stored or transferred as bytes through its `zerocopy` traits and replayed
+/// with [`CntBase::from_capture`].
+#[derive(Copy, Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct FrameCapture {
+    /// Physical count.
+    pub physical_count: u64,
+    /// Virtual count.
+    pub virtual_count: u64,
+    /// Frequency in Hz.
+    pub frequency: u32,
+    /// Second view access rights.
+    pub el0_access: CntEl0Acr,
+    /// Virtual offset.
+    pub virtual_offset: u64,
+    /// Physical timer registers.
+    pub physical_timer: TimerRegsSnapshot,
+    /// Virtual timer registers.
+    pub virtual_timer: TimerRegsSnapshot,
 }
 
 /// CntEl0Base frame is identical to the CntBase frame, except that CNTVOFF, CNTEL0ACR registers are
@@ -256,18 +893,78 @@ pub struct CntEl0Base {
     cntv: TimerRegs,
     reserved: [u32; 996],
     /// 0xfd0 Counter ID registers
-    counter_id: [ReadPure<u32>; 12],
+    counter_id: [ReadPure<u32>; COUNTER_ID_COUNT],
 }
 
 /// Driver for the CNTControlBase block.
 pub struct GenericTimerControl<'a> {
     regs: UniqueMmioPointer<'a, CntControlBase>,
+    counter_width: u8,
 }
 
 impl<'a> GenericTimerControl<'a> {
-    /// Creates new instance.
+    /// Creates new instance of a 64-bit counter.
     pub fn new(regs: UniqueMmioPointer<'a, CntControlBase>) -> Self {
-        Self { regs }
+        Self::with_counter_width(regs, 64)
+    }
+
+    /// Creates new instance of a counter which is `counter_width` bits wide and rolls over after
+    /// reaching [`Self::max_count`].
+    ///
+    /// The width of the counter is not discoverable from the registers. The architecture requires
+    /// at least 56 bits, and 64 bits from Armv8.6.
+    ///
+    /// Panics if `counter_width` is not in the range 1..=64.
+    pub fn with_counter_width(
+        regs: UniqueMmioPointer<'a, CntControlBase>,
+        counter_width: u8,
+    ) -> Self {
+        assert!((1..=64).contains(&counter_width));
+
+        Self {
+            regs,
+            counter_width,
+        }
+    }
+
+    /// Creates new instance from the base address of the CNTControlBase frame.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the properly aligned address of a CNTControlBase frame, which is mapped as
+    /// device memory and valid to read and write with volatile operations for the lifetime `'a`.
+    /// There must not be any other aliases which are used to access the frame while this instance
+    /// exists.
+    ///
+    /// Like [`Self::new`], this assumes a 64-bit counter. Use [`Self::with_counter_width`] for a
+    /// narrower counter.
+    pub unsafe fn from_base(base: NonNull<CntControlBase>) -> Self {
+        // SAFETY: The caller guarantees that `base` points to a valid and exclusively owned frame.
+        Self::new(unsafe { UniqueMmioPointer::new(base) })
+    }
+
+    /// Creates new instance after checking that the Counter ID registers identify a valid
+    /// component, which confirms that `regs` points to a genuine CNTControlBase frame.
+    ///
+    /// Like [`Self::new`], this assumes a 64-bit counter. For a narrower counter, create the
+    /// instance with [`Self::with_counter_width`] and check [`Self::counter_id`] with
+    /// [`CounterId::verify`].
+    pub fn try_new(regs: UniqueMmioPointer<'a, CntControlBase>) -> Result<Self, TimerError> {
+        let control = Self::new(regs);
+        control.counter_id().verify()?;
+
+        Ok(control)
+    }
+
+    /// Reads the Counter ID registers.
+    pub fn counter_id(&self) -> CounterId {
+        CounterId::read(field_shared!(self.regs, counter_id))
+    }
+
+    /// Gets the JEP106 code of the manufacturer from the Counter ID registers, see
+    /// [`CounterId::manufacturer`].
+    pub fn manufacturer(&self) -> Option<Jep106Id> {
+        self.counter_id().manufacturer()
     }
 
     /// Enables or disables the timer.
@@ -277,6 +974,39 @@ impl<'a> GenericTimerControl<'a> {
         field!(self.regs, cntcr).write(cntcr);
     }
 
+    /// Checks whether the counter is enabled in CNTCR.
+    pub fn is_enabled(&self) -> bool {
+        field_shared!(self.regs, cntcr).read().contains(CntCr::EN)
+    }
+
+    /// Requests the counter to halt while the debug halt signal is asserted, e.g. to stop the count
+    /// while single-stepping with a debugger.
+    pub fn set_halt_on_debug(&mut self, enable: bool) {
+        let mut cntcr = field!(self.regs, cntcr).read();
+        cntcr.set(CntCr::HDBG, enable);
+        field!(self.regs, cntcr).write(cntcr);
+    }
+
+    /// Checks whether the counter is halted for debug according to CNTSR. The status reflects the
+    /// effective state of the counter, so it may lag behind [`Self::set_halt_on_debug`].
+    ///
+    /// Unlike [`Self::is_halted`], this ignores the request in CNTCR, so it still reports a halt
+    /// after the request has been withdrawn but before the counter has resumed.
+    pub fn halted_for_debug(&self) -> bool {
+        field_shared!(self.regs, cntsr).read().contains(CntSr::HDBG)
+    }
+
+    /// Checks whether the counter is currently halted for debug, i.e. halt-on-debug is requested in
+    /// CNTCR and acknowledged in CNTSR.
+    ///
+    /// This is stricter than [`Self::halted_for_debug`], which only reads the status in CNTSR. A
+    /// halt which is being released, i.e. the request is already cleared but the status is not, is
+    /// not reported by this method.
+    pub fn is_halted(&self) -> bool {
+        field_shared!(self.regs, cntcr).read().contains(CntCr::HDBG)
+            && field_shared!(self.regs, cntsr).read().contains(CntSr::HDBG)
+    }
+
     /// Sets the number of the entry in the Frequency modes table to select.
     pub fn request_frequency(&mut self, index: usize) {
         let mut cntcr = field!(self.regs, cntcr).read();
@@ -284,19 +1014,166 @@ impl<'a> GenericTimerControl<'a> {
         field!(self.regs, cntcr).write(cntcr);
     }
 
+    /// Selects the entry of the Frequency modes table and waits until the counter acknowledges the
+    /// change, which might never happen if the counter is broken.
+    pub fn request_frequency_blocking(&mut self, index: usize) {
+        self.request_frequency(index);
+
+        while self.frequency_index() != index {
+            spin_loop();
+        }
+    }
+
+    /// Selects the entry of the Frequency modes table and waits until the counter acknowledges the
+    /// change, polling the status at most `max_spins` times.
+    ///
+    /// Returns [`TimerError::FrequencyNotAcknowledged`] if the change is not acknowledged in time.
+    /// The request is left in place in this case.
+    pub fn request_frequency_timeout(
+        &mut self,
+        index: usize,
+        max_spins: u32,
+    ) -> Result<(), TimerError> {
+        self.request_frequency(index);
+        self.wait_for_frequency(index, max_spins)
+    }
+
+    /// Waits until the counter acknowledges the entry of the Frequency modes table at `index`,
+    /// polling the status at most `max_spins` times with a spin loop hint in between.
+    ///
+    /// Returns [`TimerError::FrequencyNotAcknowledged`] if the entry is not acknowledged in time.
+    pub fn wait_for_frequency(&self, index: usize, max_spins: u32) -> Result<(), TimerError> {
+        for _ in 0..max_spins {
+            if self.frequency_index() == index {
+                return Ok(());
+            }
+
+            spin_loop();
+        }
+
+        Err(TimerError::FrequencyNotAcknowledged)
+    }
+
     /// Gets currently selected entry index in the Frequency modes table.
     pub fn frequency_index(&self) -> usize {
         field_shared!(self.regs, cntsr).read().fcack()
     }
 
+    /// Checks whether a frequency change is in progress or was rejected, i.e. the entry requested
+    /// in CNTCR.FCREQ differs from the entry acknowledged in CNTSR.FCACK.
+    pub fn frequency_change_pending(&self) -> bool {
+        field_shared!(self.regs, cntcr).read().fcreq() != self.frequency_index()
+    }
+
     /// Gets timer count value.
+    ///
+    /// When scaling is enabled the counter is incremented by the scaling factor on each tick, so
+    /// the count value already reflects the scaling and advances at [`Self::effective_frequency`].
+    ///
+    /// This is a single 64-bit read of CNTCV without barriers, which is the cheapest way to read
+    /// the count. It is single-copy atomic on 64-bit targets. The read is not ordered against
+    /// surrounding memory accesses, so a barrier is needed if the count must be sampled strictly
+    /// before or after other accesses.
     pub fn count(&self) -> u64 {
         field_shared!(self.regs, cntcv).read()
     }
 
+    /// Gets the width of the counter in bits.
+    pub fn counter_width(&self) -> u8 {
+        self.counter_width
+    }
+
+    /// Gets the largest count value before the counter rolls over to zero.
+    pub fn max_count(&self) -> u64 {
+        u64::MAX >> (64 - self.counter_width)
+    }
+
+    /// Calculates the number of ticks from `start` to `end`, taking a single rollover of the
+    /// counter into account.
+    fn ticks_between(&self, start: u64, end: u64) -> u64 {
+        end.wrapping_sub(start) & self.max_count()
+    }
+
+    /// Checks that the counter is running by reading the count before and after spinning `spins`
+    /// times. The check uses a spin loop rather than a timer, as timers depend on the counter.
+    ///
+    /// Returns [`TimerError::CounterRanBackwards`] if the second count is behind the first one, or
+    /// [`TimerError::CounterStalled`] if the counter advanced by less than `min_delta` ticks.
+    pub fn self_test(&self, min_delta: u64, spins: u32) -> Result<(), TimerError> {
+        let start = self.count();
+        for _ in 0..spins {
+            spin_loop();
+        }
+        let delta = self.ticks_between(start, self.count());
+
+        if delta > self.max_count() >> 1 {
+            Err(TimerError::CounterRanBackwards)
+        } else if delta < min_delta {
+            Err(TimerError::CounterStalled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Gets the current count value as an [`Instant`], using the effective frequency and the width
+    /// of the counter, so the arithmetic of the instant takes a rollover into account.
+    pub fn now(&self) -> Instant {
+        Instant::with_max_count(self.count(), self.effective_frequency(), self.max_count())
+    }
+
+    /// Calculates the count value `duration` after the current count, using the effective frequency
+    /// of the counter, without programming any timer. The result saturates at
+    /// [`Self::max_count`].
+    ///
+    /// The result can be stored as an absolute deadline and later programmed with
+    /// [`TimerInterface::set_compare_value`].
+    pub fn future_count(&self, duration: Duration) -> u64 {
+        self.count()
+            .saturating_add(duration_to_ticks(duration, self.effective_frequency()))
+            .min(self.max_count())
+    }
+
+    /// Runs `f` and measures its execution time using the counter.
+    ///
+    /// The elapsed time is calculated using the effective frequency of the counter. A rollover of
+    /// the counter during `f` is taken into account using the width of the counter.
+    pub fn time<R>(&self, f: impl FnOnce() -> R) -> (R, Duration) {
+        let start = self.count();
+        let result = f();
+        let end = self.count();
+
+        (
+            result,
+            ticks_to_duration(self.ticks_between(start, end), self.effective_frequency()),
+        )
+    }
+
+    /// Estimates the number of spin loop iterations which take approximately `duration`.
+    ///
+    /// `approx_ticks_per_spin` is the rough duration of a single iteration, measured in counter
+    /// ticks at the effective frequency of the counter. Both the ticks of `duration` and the result
+    /// are rounded up, so the budget is not shorter than the requested duration as long as an
+    /// iteration takes at least `approx_ticks_per_spin` ticks.
+    pub fn spins_for(&self, duration: Duration, approx_ticks_per_spin: u32) -> u64 {
+        duration_to_ticks_ceil(duration, self.effective_frequency())
+            .div_ceil(approx_ticks_per_spin.max(1).into())
+    }
+
     /// Sets timer count value.
+    ///
+    /// Writing the count while the counter is enabled is UNPREDICTABLE, so the counter is disabled
+    /// around the write if it is running, and re-enabled afterwards.
     pub fn set_count(&mut self, count: u64) {
+        let enabled = self.is_enabled();
+        if enabled {
+            self.set_enable(false);
+        }
+
         field!(self.regs, cntcv).write(count);
+
+        if enabled {
+            self.set_enable(true);
+        }
     }
 
     /// Checks whether scaling is implemented by the timer.
@@ -304,113 +1181,551 @@ impl<'a> GenericTimerControl<'a> {
         field_shared!(self.regs, cntid).read().scaling_implemented()
     }
 
+    /// Checks whether scaling is enabled.
+    pub fn scaling_enabled(&self) -> bool {
+        field_shared!(self.regs, cntcr).read().contains(CntCr::SCEN)
+    }
+
+    /// Gets the rate in Hz at which the count value advances. This is the frequency of the
+    /// currently selected frequency mode, multiplied by the scaling factor if scaling is enabled.
+    /// The result saturates at `u32::MAX`.
+    pub fn effective_frequency(&self) -> u32 {
+        let frequency = self.frequency();
+
+        if self.scaling_enabled() {
+            self.scale_decoded().scale_frequency(frequency)
+        } else {
+            frequency
+        }
+    }
+
     /// Gets scale value.
     pub fn scale(&self) -> u32 {
         field_shared!(self.regs, cntscr).read()
     }
 
+    /// Gets the decoded scaling factor.
+    pub fn scale_decoded(&self) -> Scale {
+        Scale::from_bits(self.scale())
+    }
+
+    /// Gets the scaling factor as a floating point number.
+    pub fn scale_as_f64(&self) -> f64 {
+        self.scale_decoded().as_f64()
+    }
+
+    /// Gets the scaling factor as a `(numerator, denominator)` pair, see [`Scale::ratio`].
+    pub fn scale_ratio(&self) -> (u32, u32) {
+        self.scale_decoded().ratio()
+    }
+
     /// Sets scale and enable scaling.
-    pub fn enable_scaling(&mut self, scale: u32) {
+    ///
+    /// Returns [`TimerError::ScalingUnsupported`] without writing any register if FEAT_CNTSC is not
+    /// implemented, as the scale and enable bit would be silently ignored.
+    pub fn enable_scaling(&mut self, scale: u32) -> Result<(), TimerError> {
+        if !self.scaling_implemented() {
+            return Err(TimerError::ScalingUnsupported);
+        }
+
         field!(self.regs, cntscr).write(scale);
         let cntcr = field!(self.regs, cntcr).read();
         field!(self.regs, cntcr).write(cntcr | CntCr::SCEN);
+
+        Ok(())
     }
 
-    /// Disables scaling.
+    /// Disables scaling. Disabling scaling when it is already disabled has no effect.
     pub fn disable_scaling(&mut self) {
-        let cntcr = field!(self.regs, cntcr).read();
-        field!(self.regs, cntcr).write(cntcr - CntCr::SCEN);
+        let mut cntcr = field!(self.regs, cntcr).read();
+        cntcr.remove(CntCr::SCEN);
+        field!(self.regs, cntcr).write(cntcr);
         field!(self.regs, cntscr).write(0);
     }
 
+    /// Captures the scaling configuration and the selected frequency mode.
+    pub fn scaling_snapshot(&self) -> ScalingState {
+        ScalingState {
+            scale: self.scale_decoded(),
+            scaling_enabled: self.scaling_enabled(),
+            frequency_index: self.frequency_index(),
+        }
+    }
+
+    /// Restores the scaling configuration and the selected frequency mode from a snapshot.
+    ///
+    /// Scaling is disabled while the scale value and frequency mode are written and it is only
+    /// re-enabled afterwards, so the counter never runs with a scale value that doesn't belong to
+    /// the selected frequency mode.
+    pub fn restore_scaling(&mut self, state: &ScalingState) {
+        self.disable_scaling();
+        field!(self.regs, cntscr).write(state.scale.bits());
+        self.request_frequency(state.frequency_index);
+
+        if state.scaling_enabled {
+            let cntcr = field!(self.regs, cntcr).read();
+            field!(self.regs, cntcr).write(cntcr | CntCr::SCEN);
+        }
+    }
+
     /// Indicates the base frequency of the system counter in Hz.
     pub fn base_frequency(&self) -> u32 {
         field_shared!(self.regs, cntfid).get(0).unwrap().read()
     }
 
+    /// Gets the frequency of the currently selected frequency mode in Hz. Returns 0 if the
+    /// acknowledged frequency mode is not implemented.
+    pub fn frequency(&self) -> u32 {
+        field_shared!(self.regs, cntfid)
+            .get(self.frequency_index())
+            .map_or(0, |frequency| frequency.read())
+    }
+
     /// Gets frequency mode of the given index in Hz. The availablity of the frequency mode is
     /// implementation defined.
+    ///
+    /// Returns `None` if the entry is zero or `index` is outside of the table, i.e. not in the
+    /// range `0..40`.
     pub fn frequency_mode(&self, index: usize) -> Option<u32> {
-        let frequency = field_shared!(self.regs, cntfid).get(index).unwrap().read();
-
-        if frequency != 0 {
-            Some(frequency)
-        } else {
-            None
-        }
+        field_shared!(self.regs, cntfid)
+            .get(index)
+            .map(|frequency| frequency.read())
+            .filter(|frequency| *frequency != 0)
     }
 
     /// Sets frequency mode of the given index. The availablity of the frequency mode is
     /// implementation defined.
-    pub fn set_frequency_mode(&mut self, index: usize, frequency: u32) {
+    ///
+    /// Returns [`TimerError::FrequencyModeUnavailable`] if `index` is outside of the table, i.e.
+    /// not in the range `0..40`.
+    pub fn set_frequency_mode(&mut self, index: usize, frequency: u32) -> Result<(), TimerError> {
         field!(self.regs, cntfid)
             .get(index)
-            .unwrap()
-            .write(frequency)
+            .ok_or(TimerError::FrequencyModeUnavailable)?
+            .write(frequency);
+
+        Ok(())
     }
-}
 
-/// Driver for the CNTCTLBase block.
-pub struct GenericTimerCtl<'a> {
-    regs: UniqueMmioPointer<'a, CntCtlBase>,
-}
+    /// Iterates over the `(index, frequency)` pairs of the Frequency modes table, where the
+    /// frequency is in Hz.
+    ///
+    /// The table is terminated by the first zero entry. The entry at index 0 is the base frequency,
+    /// which is required by the architecture, so it is always included.
+    pub fn frequency_modes(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        let cntfid = field_shared!(self.regs, cntfid);
+
+        (0..FREQUENCY_MODE_COUNT)
+            .map(move |index| (index, cntfid.get(index).unwrap().read()))
+            .take_while(|&(index, frequency)| index == 0 || frequency != 0)
+    }
 
-impl<'a> GenericTimerCtl<'a> {
-    /// Creates new instance.
-    pub fn new(regs: UniqueMmioPointer<'a, CntCtlBase>) -> Self {
-        Self { regs }
+    /// Reads the Frequency modes table into `table`, where entry `n` is the frequency of mode `n`
+    /// in Hz, and returns the number of valid entries.
+    ///
+    /// Reading stops at the first zero entry, including the base frequency at index 0, so an
+    /// unprogrammed table has no valid entries. The entries of `table` after the valid ones are
+    /// left unchanged. If `table` is shorter than the Frequency modes table, only the first
+    /// `table.len()` entries are read.
+    pub fn frequency_table(&self, table: &mut [u32]) -> usize {
+        let mut count = 0;
+        let frequencies = self
+            .frequency_modes()
+            .map(|(_, frequency)| frequency)
+            .take_while(|&frequency| frequency != 0);
+
+        for (entry, frequency) in table.iter_mut().zip(frequencies) {
+            *entry = frequency;
+            count += 1;
+        }
+
+        count
     }
 
-    /// Gets counter frequency in Hz.
-    pub fn frequency(&self) -> u32 {
-        field_shared!(self.regs, cntfrq).read()
+    /// Captures the readable registers of the frame.
+    pub fn capture(&self) -> ControlCapture {
+        let cntfid = field_shared!(self.regs, cntfid);
+
+        ControlCapture {
+            control: field_shared!(self.regs, cntcr).read(),
+            status: field_shared!(self.regs, cntsr).read(),
+            count: self.count(),
+            scale: self.scale(),
+            id: field_shared!(self.regs, cntid).read(),
+            frequency_modes: core::array::from_fn(|index| cntfid.get(index).unwrap().read()),
+        }
     }
 
-    /// Sets counter frequency in Hz.
+    /// Fills `buffer` with the `(index, frequency)` pairs of the populated entries of the Frequency
+    /// modes table, sorted by ascending frequency in Hz, and returns the filled part of the buffer.
+    ///
+    /// The table is walked by [`Self::frequency_modes`]. If the buffer is shorter than the table,
+    /// only the first `buffer.len()` entries of the table are considered.
+    pub fn frequency_modes_sorted<'b>(&self, buffer: &'b mut [(usize, u32)]) -> &'b [(usize, u32)] {
+        let mut count = 0;
+
+        for (entry, mode) in buffer.iter_mut().zip(self.frequency_modes()) {
+            *entry = mode;
+            count += 1;
+        }
+
+        let modes = &mut buffer[..count];
+        modes.sort_unstable_by_key(|&(index, frequency)| (frequency, index));
+        modes
+    }
+
+    /// Initializes the counter according to the configuration.
+    ///
+    /// The configuration is validated before any register is written. The counter is disabled while
+    /// the frequency modes table, scaling, frequency mode and count value are programmed, and it is
+    /// enabled afterwards if requested by the configuration.
+    ///
+    /// The selected mode must be within the table terminated by its first zero entry, i.e. all
+    /// entries up to and including the selected one must be non-zero, as the hardware never selects
+    /// an entry after the terminator.
+    pub fn initialize(&mut self, config: &InitConfig) -> Result<(), TimerError> {
+        let mode_populated = match config.frequency_table {
+            Some(table) if table.len() > FREQUENCY_MODE_COUNT => {
+                return Err(TimerError::FrequencyTableTooLong);
+            }
+            Some(table) => {
+                let len = table
+                    .iter()
+                    .position(|frequency| *frequency == 0)
+                    .unwrap_or(table.len());
+                if table[len..].iter().any(|frequency| *frequency != 0) {
+                    return Err(TimerError::FrequencyTableGap);
+                }
+
+                config.mode < len
+            }
+            None => (0..=config.mode).all(|index| self.frequency_mode(index).is_some()),
+        };
+
+        if !mode_populated {
+            return Err(TimerError::FrequencyModeUnavailable);
+        }
+
+        let scaling_implemented = self.scaling_implemented();
+        if config.scaling.is_some() && !scaling_implemented {
+            return Err(TimerError::ScalingUnsupported);
+        }
+
+        self.set_enable(false);
+
+        if let Some(table) = config.frequency_table {
+            for (index, frequency) in table.iter().enumerate() {
+                self.set_frequency_mode(index, *frequency)?;
+            }
+
+            if table.len() < FREQUENCY_MODE_COUNT {
+                self.set_frequency_mode(table.len(), 0)?;
+            }
+        }
+
+        if scaling_implemented {
+            match config.scaling {
+                Some(scale) => self.enable_scaling(scale.bits())?,
+                None => self.disable_scaling(),
+            }
+        }
+
+        self.request_frequency(config.mode);
+
+        if let Some(count) = config.initial_count {
+            self.set_count(count);
+        }
+
+        self.set_enable(config.enable);
+
+        Ok(())
+    }
+}
+
+/// Ring of the last `N` count samples, for post-mortem analysis of timing issues.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CountTrace<const N: usize> {
+    samples: [u64; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> CountTrace<N> {
+    /// Creates new empty instance.
+    pub const fn new() -> Self {
+        Self {
+            samples: [0; N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Records the current count value of the counter, overwriting the oldest sample if the trace
+    /// is full.
+    pub fn record(&mut self, control: &GenericTimerControl) {
+        if N == 0 {
+            return;
+        }
+
+        self.samples[self.next] = control.count();
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Returns the number of recorded samples.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether no samples have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the recorded samples, from the newest to the oldest.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        (1..=self.len).map(move |age| self.samples[(self.next + N - age) % N])
+    }
+}
+
+impl<const N: usize> Default for CountTrace<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read-only driver for the CNTReadBase block.
+pub struct GenericTimerRead<'a> {
+    regs: UniqueMmioPointer<'a, CntReadBase>,
+}
+
+impl<'a> GenericTimerRead<'a> {
+    /// Creates new instance.
+    pub fn new(regs: UniqueMmioPointer<'a, CntReadBase>) -> Self {
+        Self { regs }
+    }
+
+    /// Creates new instance from the base address of the CNTReadBase frame.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the properly aligned address of a CNTReadBase frame, which is mapped as
+    /// device memory and valid to read and write with volatile operations for the lifetime `'a`.
+    /// There must not be any other aliases which are used to access the frame while this instance
+    /// exists.
+    pub unsafe fn from_base(base: NonNull<CntReadBase>) -> Self {
+        // SAFETY: The caller guarantees that `base` points to a valid and exclusively owned frame.
+        Self::new(unsafe { UniqueMmioPointer::new(base) })
+    }
+
+    /// Gets timer count value.
+    pub fn count(&self) -> u64 {
+        field_shared!(self.regs, cntcv).read()
+    }
+
+    /// Reads the Counter ID registers.
+    pub fn counter_id(&self) -> CounterId {
+        CounterId::read(field_shared!(self.regs, counter_id))
+    }
+
+    /// Gets the component ID from the Counter ID registers.
+    pub fn component_id(&self) -> u32 {
+        self.counter_id().component_id()
+    }
+}
+
+/// Driver for the CNTCTLBase block.
+pub struct GenericTimerCtl<'a> {
+    regs: UniqueMmioPointer<'a, CntCtlBase>,
+}
+
+impl<'a> GenericTimerCtl<'a> {
+    /// Creates new instance.
+    pub fn new(regs: UniqueMmioPointer<'a, CntCtlBase>) -> Self {
+        Self { regs }
+    }
+
+    /// Creates new instance from the base address of the CNTCTLBase frame.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the properly aligned address of a CNTCTLBase frame, which is mapped as device
+    /// memory and valid to read and write with volatile operations for the lifetime `'a`. There
+    /// must not be any other aliases which are used to access the frame while this instance exists.
+    pub unsafe fn from_base(base: NonNull<CntCtlBase>) -> Self {
+        // SAFETY: The caller guarantees that `base` points to a valid and exclusively owned frame.
+        Self::new(unsafe { UniqueMmioPointer::new(base) })
+    }
+
+    /// Gets counter frequency in Hz.
+    pub fn frequency(&self) -> u32 {
+        field_shared!(self.regs, cntfrq).read()
+    }
+
+    /// Sets counter frequency in Hz.
     pub fn set_frequency(&mut self, frequency: u32) {
         field!(self.regs, cntfrq).write(frequency);
     }
 
+    /// Returns [`TimerError::FrameOutOfRange`] if `index` is not a valid timer frame index.
+    fn check_frame(index: usize) -> Result<(), TimerError> {
+        if index < FRAME_COUNT {
+            Ok(())
+        } else {
+            Err(TimerError::FrameOutOfRange)
+        }
+    }
+
     /// Gets non-secure access state.
     pub fn non_secure_access(&self, index: usize) -> bool {
-        assert!(index < 8);
+        assert!(index < FRAME_COUNT);
 
         let cntnsar = field_shared!(self.regs, cntnsar).read();
-        cntnsar & (1 << index) != 0
+        cntnsar.bits() & (1 << index) != 0
+    }
+
+    /// Gets non-secure access state, or returns [`TimerError::FrameOutOfRange`] if `index` is not a
+    /// valid timer frame index.
+    pub fn try_non_secure_access(&self, index: usize) -> Result<bool, TimerError> {
+        Self::check_frame(index)?;
+
+        Ok(self.non_secure_access(index))
     }
 
     /// Provides the highest-level control of whether frames CNTBaseN and CNTEL0BaseN are accessible
     /// by Non-secure accesses.
     pub fn set_non_secure_access(&mut self, index: usize, enable: bool) {
-        assert!(index < 8);
+        assert!(index < FRAME_COUNT);
 
         let mut cntnsar = field_shared!(self.regs, cntnsar).read();
-        if enable {
-            cntnsar |= 1 << index;
-        } else {
-            cntnsar &= !(1 << index);
-        }
+        cntnsar.set(CntNsar::from_bits_retain(1 << index), enable);
+        field!(self.regs, cntnsar).write(cntnsar);
+    }
+
+    /// Sets non-secure access state like [`Self::set_non_secure_access`], or returns
+    /// [`TimerError::FrameOutOfRange`] if `index` is not a valid timer frame index.
+    pub fn try_set_non_secure_access(
+        &mut self,
+        index: usize,
+        enable: bool,
+    ) -> Result<(), TimerError> {
+        Self::check_frame(index)?;
+        self.set_non_secure_access(index, enable);
+
+        Ok(())
+    }
+
+    /// Gets the non-secure access state of all frames, with bit `n` corresponding to frame `n`.
+    pub fn non_secure_mask(&self) -> u8 {
+        field_shared!(self.regs, cntnsar).read().bits() as u8
+    }
+
+    /// Sets the non-secure access state of all frames in a single write, with bit `n` corresponding
+    /// to frame `n`.
+    pub fn set_non_secure_mask(&mut self, mask: u8) {
+        field!(self.regs, cntnsar).write(CntNsar::from_bits_retain(mask.into()));
+    }
+
+    /// Gets the non-secure access state of all frames.
+    pub fn non_secure_access_flags(&self) -> CntNsar {
+        field_shared!(self.regs, cntnsar).read()
+    }
+
+    /// Sets the non-secure access state of all frames in a single write.
+    pub fn set_non_secure_access_flags(&mut self, cntnsar: CntNsar) {
         field!(self.regs, cntnsar).write(cntnsar);
     }
 
     /// Queries features of the timer.
     pub fn features(&self, index: usize) -> Features {
-        assert!(index < 8);
+        assert!(index < FRAME_COUNT);
 
         let cnttidr = field_shared!(self.regs, cnttidr).read();
-        Features::from_bits_truncate((cnttidr >> (index * 8)) as u8)
+        Features::from_bits_truncate(((cnttidr >> (index * 4)) & 0xf) as u8)
+    }
+
+    /// Queries features of the timer, or returns [`TimerError::FrameOutOfRange`] if `index` is not
+    /// a valid timer frame index.
+    pub fn try_features(&self, index: usize) -> Result<Features, TimerError> {
+        Self::check_frame(index)?;
+
+        Ok(self.features(index))
+    }
+
+    /// Checks whether the timer frame has virtual capability, i.e. the virtual timer and the
+    /// virtual offset registers are implemented.
+    pub fn has_virtual(&self, index: usize) -> bool {
+        self.features(index).contains(Features::VIRTUAL)
+    }
+
+    /// Checks whether the timer frame has a second view, CNTEL0BaseN.
+    pub fn has_el0_view(&self, index: usize) -> bool {
+        self.features(index).contains(Features::CNTEL0BASE)
+    }
+
+    /// Returns the indices of the implemented timer frames in ascending order, i.e. the frames
+    /// which have [`Features::IMPLEMENTED`] set. Frames which are not implemented must not be
+    /// accessed.
+    pub fn implemented_frames(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..FRAME_COUNT).filter(|index| self.features(*index).contains(Features::IMPLEMENTED))
+    }
+
+    /// Returns the highest index of the implemented timer frames, or `None` if no frame is
+    /// implemented.
+    pub fn highest_implemented_frame(&self) -> Option<usize> {
+        (0..FRAME_COUNT)
+            .rev()
+            .find(|index| self.features(*index).contains(Features::IMPLEMENTED))
     }
 
     /// Gets current top-level access controls for the elements of a timer frame.
     pub fn access_control(&self, index: usize) -> CntAcr {
+        assert!(index < FRAME_COUNT);
+
         field_shared!(self.regs, cntacr).get(index).unwrap().read()
     }
 
+    /// Gets current top-level access controls for the elements of a timer frame, or returns
+    /// [`TimerError::FrameOutOfRange`] if `index` is not a valid timer frame index.
+    pub fn try_access_control(&self, index: usize) -> Result<CntAcr, TimerError> {
+        Self::check_frame(index)?;
+
+        Ok(self.access_control(index))
+    }
+
     /// Sets top-level access controls for the elements of a timer frame.
     pub fn set_access_control(&mut self, index: usize, cntacr: CntAcr) {
+        assert!(index < FRAME_COUNT);
+
         field!(self.regs, cntacr).get(index).unwrap().write(cntacr);
     }
 
+    /// Sets top-level access controls for the elements of a timer frame, or returns
+    /// [`TimerError::FrameOutOfRange`] if `index` is not a valid timer frame index.
+    pub fn try_set_access_control(
+        &mut self,
+        index: usize,
+        cntacr: CntAcr,
+    ) -> Result<(), TimerError> {
+        Self::check_frame(index)?;
+        self.set_access_control(index, cntacr);
+
+        Ok(())
+    }
+
+    /// Grants full access to the elements of a timer frame, see [`CntAcr::all_access`]. The
+    /// Non-secure access state of the frame is not changed.
+    pub fn grant_full_access(&mut self, index: usize) {
+        self.set_access_control(index, CntAcr::all_access());
+    }
+
+    /// Applies an access policy to a timer frame by setting its access controls and whether it is
+    /// accessible by Non-secure accesses.
+    pub fn apply_policy(&mut self, index: usize, policy: FramePolicy) {
+        self.set_access_control(index, policy.access_control());
+        self.set_non_secure_access(index, policy != FramePolicy::NoAccess);
+    }
+
     /// Gets the 64-bit virtual offset for frame CNTBase.
     pub fn virtual_offset(&self, index: usize) -> u64 {
         field_shared!(self.regs, cntvoff).get(index).unwrap().read()
@@ -421,12 +1736,48 @@ impl<'a> GenericTimerCtl<'a> {
     pub fn set_virtual_offset(&mut self, index: usize, offset: u64) {
         field!(self.regs, cntvoff).get(index).unwrap().write(offset);
     }
+
+    /// Captures the readable registers of the frame.
+    pub fn capture(&self) -> CtlCapture {
+        let cntacr = field_shared!(self.regs, cntacr);
+        let cntvoff = field_shared!(self.regs, cntvoff);
+
+        CtlCapture {
+            frequency: self.frequency(),
+            non_secure_access: self.non_secure_access_flags(),
+            timer_id: field_shared!(self.regs, cnttidr).read(),
+            access_control: core::array::from_fn(|index| cntacr.get(index).unwrap().read()),
+            virtual_offset: core::array::from_fn(|index| cntvoff.get(index).unwrap().read()),
+        }
+    }
+
+    /// Gets the virtual offset for frame CNTBase as a `Duration`, using the counter frequency. The
+    /// result is truncated to whole nanoseconds.
+    ///
+    /// Panics if the counter frequency is zero.
+    pub fn virtual_offset_duration(&self, index: usize) -> Duration {
+        self.ticks_to_duration(self.virtual_offset(index))
+    }
+
+    /// Sets the virtual offset for frame CNTBase to `offset`, converted to ticks using the counter
+    /// frequency. The offset is rounded to the nearest tick, so an offset shorter than half a tick
+    /// is written as zero.
+    pub fn set_virtual_offset_duration(&mut self, index: usize, offset: Duration) {
+        self.set_virtual_offset(index, self.duration_to_ticks(offset));
+    }
+}
+
+impl CounterFrequency for GenericTimerCtl<'_> {
+    fn frequency(&self) -> u32 {
+        self.frequency()
+    }
 }
 
 /// `TimerInterface` implementation of the MMIO based physical or virtual timer instance of the
 /// CNTBase block.
 pub struct MmioTimer<'a> {
     regs: UniqueMmioPointer<'a, TimerRegs>,
+    count: SharedMmioPointer<'a, ReadPure<u64>>,
     frequency: u32,
 }
 
@@ -440,9 +1791,33 @@ impl<'a> TimerInterface for MmioTimer<'a> {
         field_shared!(self.regs, tval).read()
     }
 
+    fn set_timer_value(&mut self, value: u32) {
+        field!(self.regs, tval).write(value);
+    }
+
     fn frequency(&self) -> u32 {
         self.frequency
     }
+
+    fn count(&self) -> u64 {
+        self.count.read()
+    }
+
+    fn compare_value(&self) -> u64 {
+        field_shared!(self.regs, cval).read()
+    }
+
+    fn set_compare_value(&mut self, value: u64) {
+        field!(self.regs, cval).write(value);
+    }
+
+    fn control(&self) -> TimerControl {
+        field_shared!(self.regs, ctl).read()
+    }
+
+    fn set_control(&mut self, control: TimerControl) {
+        field!(self.regs, ctl).write(control);
+    }
 }
 
 /// Driver for the CNTBase timer block.
@@ -456,11 +1831,32 @@ impl<'a> GenericTimerCnt<'a> {
         Self { regs }
     }
 
+    /// Creates new instance from the base address of the CNTBaseN frame.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the properly aligned address of a CNTBaseN frame, which is mapped as device
+    /// memory and valid to read and write with volatile operations for the lifetime `'a`. There
+    /// must not be any other aliases which are used to access the frame while this instance exists.
+    pub unsafe fn from_base(base: NonNull<CntBase>) -> Self {
+        // SAFETY: The caller guarantees that `base` points to a valid and exclusively owned frame.
+        Self::new(unsafe { UniqueMmioPointer::new(base) })
+    }
+
     /// Gets physical count.
     pub fn physical_count(&self) -> u64 {
         field_shared!(self.regs, cntpct).read()
     }
 
+    /// Returns the time elapsed since `start`, a physical count sampled earlier by
+    /// [`Self::physical_count`]. The result is truncated to whole nanoseconds.
+    ///
+    /// Less than a full wrap-around of the 64-bit count must have occurred since `start`. Panics if
+    /// the frequency is zero.
+    pub fn elapsed_since(&self, start: u64) -> Duration {
+        self.ticks_to_duration(self.physical_count().wrapping_sub(start))
+    }
+
     /// Gets virtual count.
     pub fn virtual_count(&self) -> u64 {
         field_shared!(self.regs, cntvct).read()
@@ -481,30 +1877,218 @@ impl<'a> GenericTimerCnt<'a> {
         field!(self.regs, cntel0acr).write(value)
     }
 
+    /// Allows or denies reading CNTPCT and CNTFRQ through the second view.
+    pub fn allow_el0_physical_count(&mut self, enable: bool) {
+        self.modify_el0_access(CntEl0Acr::EL0PCTEN, enable);
+    }
+
+    /// Allows or denies reading CNTVCT and CNTFRQ through the second view.
+    pub fn allow_el0_virtual_count(&mut self, enable: bool) {
+        self.modify_el0_access(CntEl0Acr::EL0VCTEN, enable);
+    }
+
+    /// Allows or denies accessing the physical timer registers through the second view.
+    pub fn allow_el0_physical_timer(&mut self, enable: bool) {
+        self.modify_el0_access(CntEl0Acr::EL0PTEN, enable);
+    }
+
+    /// Allows or denies accessing the virtual timer registers through the second view.
+    pub fn allow_el0_virtual_timer(&mut self, enable: bool) {
+        self.modify_el0_access(CntEl0Acr::EL0VTEN, enable);
+    }
+
+    /// Sets or clears `flag` in CNTEL0ACR, leaving the other bits unchanged.
+    fn modify_el0_access(&mut self, flag: CntEl0Acr, enable: bool) {
+        let mut cntel0acr = self.el0_access();
+        cntel0acr.set(flag, enable);
+        self.set_el0_access(cntel0acr);
+    }
+
+    /// Gets the registers which EL0 may access through the second view of this frame.
+    ///
+    /// The CNTEL0Base frame has no access control register of its own, its accessible registers are
+    /// determined by CNTEL0ACR of this frame. Accessing any other register through the second view
+    /// reads as zero and ignores writes.
+    pub fn el0_view_access(&self) -> El0Access {
+        self.el0_access().into()
+    }
+
     /// Gets the 64-bit virtual offset for frame CNTBase.
     pub fn virtual_offset(&self) -> u64 {
         field_shared!(self.regs, cntvoff).read()
     }
 
+    /// Converts a physical count to the corresponding virtual count using the virtual offset of
+    /// the frame. The calculation wraps around like the counter itself.
+    ///
+    /// The hardware already applies the offset to [`Self::virtual_count`], this is only needed to
+    /// relate separately sampled counts.
+    pub fn physical_to_virtual(&self, physical_count: u64) -> u64 {
+        physical_count.wrapping_sub(self.virtual_offset())
+    }
+
+    /// Converts a virtual count to the corresponding physical count using the virtual offset of
+    /// the frame. The calculation wraps around like the counter itself.
+    pub fn virtual_to_physical(&self, virtual_count: u64) -> u64 {
+        virtual_count.wrapping_add(self.virtual_offset())
+    }
+
+    /// Reads the Counter ID registers.
+    pub fn counter_id(&self) -> CounterId {
+        CounterId::read(field_shared!(self.regs, counter_id))
+    }
+
+    /// Gets the peripheral ID from the Counter ID registers.
+    pub fn peripheral_id(&self) -> u64 {
+        self.counter_id().peripheral_id()
+    }
+
+    /// Gets the component ID from the Counter ID registers.
+    pub fn component_id(&self) -> u32 {
+        self.counter_id().component_id()
+    }
+
+    /// Checks that the Counter ID registers identify a valid component, which confirms that the
+    /// frame is mapped correctly. Returns [`TimerError::ComponentMismatch`] otherwise.
+    pub fn verify_component(&self) -> Result<(), TimerError> {
+        self.counter_id().verify()
+    }
+
+    /// Captures all readable registers of the frame. The registers are read one after the other, so
+    /// the counts and the timer values are not sampled at the same instant.
+    pub fn capture(&self) -> FrameCapture {
+        FrameCapture {
+            physical_count: self.physical_count(),
+            virtual_count: self.virtual_count(),
+            frequency: self.frequency(),
+            el0_access: self.el0_access(),
+            virtual_offset: self.virtual_offset(),
+            physical_timer: TimerRegs::snapshot(field_shared!(self.regs, cntp)),
+            virtual_timer: TimerRegs::snapshot(field_shared!(self.regs, cntv)),
+        }
+    }
+
+    /// Checks whether the condition of the physical and virtual timers is met, as a
+    /// `(physical, virtual)` pair. Disabled timers are never pending.
+    pub fn pending_timers(&self) -> (bool, bool) {
+        let cntp = field_shared!(self.regs, cntp);
+        let cntv = field_shared!(self.regs, cntv);
+
+        (
+            field_shared!(cntp, ctl).read().is_pending(),
+            field_shared!(cntv, ctl).read().is_pending(),
+        )
+    }
+
+    /// Returns a bitmask with bit `n` set if `frames[n]` has a pending physical or virtual timer.
+    ///
+    /// The CNTCTLBase frame has no per-frame timer status, so the status has to be collected from
+    /// the CNTBase frames themselves.
+    pub fn pending_frames(frames: &[Self]) -> u32 {
+        frames
+            .iter()
+            .take(u32::BITS as usize)
+            .enumerate()
+            .filter(|(_, frame)| frame.pending_timers() != (false, false))
+            .fold(0, |mask, (index, _)| mask | 1 << index)
+    }
+
     /// Gets physical timer.
     pub fn physical_timer(&mut self) -> Timer<MmioTimer<'_>> {
         let frequency = self.frequency();
+        // SAFETY: `cntpct` and `cntp` are distinct fields.
+        let (count, regs) = unsafe { split_fields!(self.regs.reborrow(), cntpct, cntp) };
         Timer::new(MmioTimer {
-            regs: field!(self.regs, cntp),
+            regs,
+            count: count.into(),
             frequency,
         })
     }
 
-    /// Gets virtual timer.
+    /// Gets virtual timer. The timer condition compares against the virtual count, so the timer
+    /// uses the virtual count as the base of relative deadlines.
     pub fn virtual_timer(&mut self) -> Timer<MmioTimer<'_>> {
         let frequency = self.frequency();
+        // SAFETY: `cntvct` and `cntv` are distinct fields.
+        let (count, regs) = unsafe { split_fields!(self.regs.reborrow(), cntvct, cntv) };
         Timer::new(MmioTimer {
-            regs: field!(self.regs, cntv),
+            regs,
+            count: count.into(),
             frequency,
         })
     }
 }
 
+impl CounterFrequency for GenericTimerCnt<'_> {
+    fn frequency(&self) -> u32 {
+        self.frequency()
+    }
+}
+
+/// Driver of a complete memory-mapped Generic Timer, consisting of the CNTControlBase and
+/// CNTCTLBase blocks and the CNTBaseN frames.
+///
+/// The implemented frames are read from the Features of CNTCTLBase once on creation. Frames which
+/// are not implemented are never accessed, even if a pointer is given for them.
+pub struct GenericTimer<'a> {
+    control: GenericTimerControl<'a>,
+    ctl: GenericTimerCtl<'a>,
+    frames: [Option<GenericTimerCnt<'a>>; FRAME_COUNT],
+    implemented: u8,
+}
+
+impl<'a> GenericTimer<'a> {
+    /// Creates new instance. `frames` contains the pointer of the CNTBaseN frame at index `N`, or
+    /// `None` if the frame is not mapped.
+    pub fn new(
+        control: UniqueMmioPointer<'a, CntControlBase>,
+        ctl: UniqueMmioPointer<'a, CntCtlBase>,
+        frames: [Option<UniqueMmioPointer<'a, CntBase>>; FRAME_COUNT],
+    ) -> Self {
+        let ctl = GenericTimerCtl::new(ctl);
+        let implemented = ctl
+            .implemented_frames()
+            .fold(0, |mask, index| mask | (1 << index));
+
+        Self {
+            control: GenericTimerControl::new(control),
+            ctl,
+            frames: frames.map(|frame| frame.map(GenericTimerCnt::new)),
+            implemented,
+        }
+    }
+
+    /// Gets the driver of the CNTControlBase block.
+    pub fn control(&mut self) -> &mut GenericTimerControl<'a> {
+        &mut self.control
+    }
+
+    /// Gets the driver of the CNTCTLBase block.
+    pub fn ctl(&mut self) -> &mut GenericTimerCtl<'a> {
+        &mut self.ctl
+    }
+
+    /// Checks whether the frame is implemented, according to the Features cached on creation.
+    pub fn is_implemented(&self, index: usize) -> bool {
+        index < FRAME_COUNT && self.implemented & (1 << index) != 0
+    }
+
+    /// Returns the indices of the implemented frames in ascending order.
+    pub fn implemented_frames(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..FRAME_COUNT).filter(|index| self.is_implemented(*index))
+    }
+
+    /// Gets the driver of the CNTBaseN frame, or `None` if the frame is not implemented or its
+    /// pointer was not given.
+    pub fn frame(&mut self, index: usize) -> Option<&mut GenericTimerCnt<'a>> {
+        if !self.is_implemented(index) {
+            return None;
+        }
+
+        self.frames[index].as_mut()
+    }
+}
+
 /// Driver for the CNTEL0Base timer block.
 pub struct GenericTimerCntEl0<'a> {
     regs: UniqueMmioPointer<'a, CntEl0Base>,
@@ -516,6 +2100,19 @@ impl<'a> GenericTimerCntEl0<'a> {
         Self { regs }
     }
 
+    /// Creates new instance from the base address of the CNTEL0BaseN frame.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the properly aligned address of a CNTEL0BaseN frame, which is mapped as
+    /// device memory and valid to read and write with volatile operations for the lifetime `'a`.
+    /// There must not be any other aliases which are used to access the frame while this instance
+    /// exists.
+    pub unsafe fn from_base(base: NonNull<CntEl0Base>) -> Self {
+        // SAFETY: The caller guarantees that `base` points to a valid and exclusively owned frame.
+        Self::new(unsafe { UniqueMmioPointer::new(base) })
+    }
+
     /// Gets physical count.
     pub fn physical_count(&self) -> u64 {
         field_shared!(self.regs, cntpct).read()
@@ -534,26 +2131,43 @@ impl<'a> GenericTimerCntEl0<'a> {
     /// Gets physical timer.
     pub fn physical_timer(&mut self) -> Timer<MmioTimer<'_>> {
         let frequency = self.frequency();
+        // SAFETY: `cntpct` and `cntp` are distinct fields.
+        let (count, regs) = unsafe { split_fields!(self.regs.reborrow(), cntpct, cntp) };
         Timer::new(MmioTimer {
-            regs: field!(self.regs, cntp),
+            regs,
+            count: count.into(),
             frequency,
         })
     }
 
-    /// Gets virtual timer.
+    /// Gets virtual timer. The timer condition compares against the virtual count, so the timer
+    /// uses the virtual count as the base of relative deadlines.
     pub fn virtual_timer(&mut self) -> Timer<MmioTimer<'_>> {
         let frequency = self.frequency();
+        // SAFETY: `cntvct` and `cntv` are distinct fields.
+        let (count, regs) = unsafe { split_fields!(self.regs.reborrow(), cntvct, cntv) };
         Timer::new(MmioTimer {
-            regs: field!(self.regs, cntv),
+            regs,
+            count: count.into(),
             frequency,
         })
     }
 }
 
+impl CounterFrequency for GenericTimerCntEl0<'_> {
+    fn frequency(&self) -> u32 {
+        self.frequency()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    extern crate std;
+    use core::num::NonZeroU32;
+    use std::string::ToString;
+
     #[test]
     fn sizes() {
         assert_eq!(0x1000, core::mem::size_of::<CntControlBase>());
@@ -562,4 +2176,1100 @@ mod tests {
         assert_eq!(0x1000, core::mem::size_of::<CntBase>());
         assert_eq!(0x1000, core::mem::size_of::<CntEl0Base>());
     }
+
+    #[test]
+    fn offsets() {
+        use core::mem::offset_of;
+
+        assert_eq!(0x000, offset_of!(CntControlBase, cntcr));
+        assert_eq!(0x004, offset_of!(CntControlBase, cntsr));
+        assert_eq!(0x008, offset_of!(CntControlBase, cntcv));
+        assert_eq!(0x010, offset_of!(CntControlBase, cntscr));
+        assert_eq!(0x01c, offset_of!(CntControlBase, cntid));
+        assert_eq!(0x020, offset_of!(CntControlBase, cntfid));
+        assert_eq!(0x0c0, offset_of!(CntControlBase, impdef_0c0));
+        assert_eq!(0xfd0, offset_of!(CntControlBase, counter_id));
+
+        assert_eq!(0x000, offset_of!(CntReadBase, cntcv));
+        assert_eq!(0xfd0, offset_of!(CntReadBase, counter_id));
+
+        assert_eq!(0x000, offset_of!(CntCtlBase, cntfrq));
+        assert_eq!(0x004, offset_of!(CntCtlBase, cntnsar));
+        assert_eq!(0x008, offset_of!(CntCtlBase, cnttidr));
+        assert_eq!(0x040, offset_of!(CntCtlBase, cntacr));
+        assert_eq!(0x080, offset_of!(CntCtlBase, cntvoff));
+        assert_eq!(0x100, offset_of!(CntCtlBase, impdef_100));
+        assert_eq!(0xfc0, offset_of!(CntCtlBase, impdef_fc0));
+        assert_eq!(0xfd0, offset_of!(CntCtlBase, counter_id));
+
+        assert_eq!(0x000, offset_of!(TimerRegs, cval));
+        assert_eq!(0x008, offset_of!(TimerRegs, tval));
+        assert_eq!(0x00c, offset_of!(TimerRegs, ctl));
+
+        assert_eq!(0x000, offset_of!(CntBase, cntpct));
+        assert_eq!(0x008, offset_of!(CntBase, cntvct));
+        assert_eq!(0x010, offset_of!(CntBase, cntfrq));
+        assert_eq!(0x014, offset_of!(CntBase, cntel0acr));
+        assert_eq!(0x018, offset_of!(CntBase, cntvoff));
+        assert_eq!(0x020, offset_of!(CntBase, cntp));
+        assert_eq!(0x030, offset_of!(CntBase, cntv));
+        assert_eq!(0xfd0, offset_of!(CntBase, counter_id));
+
+        assert_eq!(0x000, offset_of!(CntEl0Base, cntpct));
+        assert_eq!(0x008, offset_of!(CntEl0Base, cntvct));
+        assert_eq!(0x010, offset_of!(CntEl0Base, cntfrq));
+        assert_eq!(0x020, offset_of!(CntEl0Base, cntp));
+        assert_eq!(0x030, offset_of!(CntEl0Base, cntv));
+        assert_eq!(0xfd0, offset_of!(CntEl0Base, counter_id));
+    }
+
+    #[test]
+    fn initialize() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntid.0 = CntId(CntId::CNTSC_IMPLEMENTED);
+        regs.cntfid[2].0 = 1_000;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        let config = InitConfig::new()
+            .frequency_table(&[50_000_000, 25_000_000])
+            .select_mode(1)
+            .initial_count(0x1234)
+            .scaling(Scale::from_bits(0x0180_0000));
+        assert_eq!(Ok(()), control.initialize(&config));
+
+        assert_eq!(
+            CntCr::EN | CntCr::SCEN | CntCr::from_bits_retain(1 << 8),
+            regs.cntcr.0
+        );
+        assert_eq!(0x1234, regs.cntcv.0);
+        assert_eq!(0x0180_0000, regs.cntscr.0);
+        assert_eq!(50_000_000, regs.cntfid[0].0);
+        assert_eq!(25_000_000, regs.cntfid[1].0);
+        assert_eq!(0, regs.cntfid[2].0);
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(
+            Ok(()),
+            control.initialize(&InitConfig::new().frequency_table(&[100, 0, 0]))
+        );
+        assert_eq!(100, regs.cntfid[0].0);
+    }
+
+    #[test]
+    fn initialize_invalid() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN;
+        regs.cntfid[0].0 = 1_000;
+        regs.cntfid[2].0 = 2_000;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        let table = [1_000; FREQUENCY_MODE_COUNT + 1];
+        assert_eq!(
+            Err(TimerError::FrequencyTableTooLong),
+            control.initialize(&InitConfig::new().frequency_table(&table))
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyModeUnavailable),
+            control.initialize(&InitConfig::new().select_mode(1))
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyModeUnavailable),
+            control.initialize(&InitConfig::new().select_mode(FREQUENCY_MODE_COUNT))
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyModeUnavailable),
+            control.initialize(&InitConfig::new().select_mode(2))
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyModeUnavailable),
+            control.initialize(
+                &InitConfig::new()
+                    .frequency_table(&[1_000, 0])
+                    .select_mode(1)
+            )
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyTableGap),
+            control.initialize(&InitConfig::new().frequency_table(&[100, 0, 200]))
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyTableGap),
+            control.initialize(
+                &InitConfig::new()
+                    .frequency_table(&[100, 0, 200])
+                    .select_mode(2)
+            )
+        );
+        assert_eq!(
+            Err(TimerError::ScalingUnsupported),
+            control.initialize(&InitConfig::new().scaling(Scale::ONE))
+        );
+
+        // Failed validation must leave the counter untouched.
+        assert_eq!(CntCr::EN, regs.cntcr.0);
+    }
+
+    #[test]
+    fn is_halted() {
+        let mut regs = CntControlBase::new_zeroed();
+        assert!(!GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).is_halted());
+
+        regs.cntcr.0 = CntCr::HDBG;
+        assert!(!GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).is_halted());
+
+        regs.cntsr.0 = CntSr::HDBG;
+        assert!(GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).is_halted());
+    }
+
+    #[test]
+    fn grant_full_access() {
+        assert_eq!(CntAcr::all(), CntAcr::all_access());
+
+        let mut regs = CntCtlBase::new_zeroed();
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        ctl.grant_full_access(2);
+        assert_eq!(CntAcr::all_access(), ctl.access_control(2));
+        assert!(!ctl.non_secure_access(2));
+        assert_eq!(CntAcr::empty(), regs.cntacr[1].0);
+    }
+
+    #[test]
+    fn cntacr_builder() {
+        assert_eq!(CntAcr::empty(), CntAcr::builder().build());
+        assert_eq!(
+            CntAcr::RWPT | CntAcr::RWVT | CntAcr::RFRQ,
+            CntAcr::builder()
+                .rw_physical_timer()
+                .rw_virtual_timer()
+                .ro_frequency()
+                .build()
+        );
+        assert_eq!(
+            CntAcr::all(),
+            CntAcr::builder()
+                .rw_physical_timer()
+                .rw_virtual_timer()
+                .ro_virtual_offset()
+                .ro_frequency()
+                .ro_virtual_count()
+                .ro_physical_count()
+                .build()
+        );
+    }
+
+    #[test]
+    fn set_count_running() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN | CntCr::HDBG;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        control.set_count(0x1234);
+        assert!(control.is_enabled());
+
+        assert_eq!(0x1234, regs.cntcv.0);
+        assert_eq!(CntCr::EN | CntCr::HDBG, regs.cntcr.0);
+    }
+
+    #[test]
+    fn wait_for_frequency() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntsr.0 = CntSr(3 << CntSr::FCACK_SHIFT);
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Ok(()), control.wait_for_frequency(3, 1));
+        assert_eq!(
+            Err(TimerError::FrequencyNotAcknowledged),
+            control.wait_for_frequency(1, 10)
+        );
+        assert_eq!(
+            Err(TimerError::FrequencyNotAcknowledged),
+            control.wait_for_frequency(3, 0)
+        );
+    }
+
+    #[test]
+    fn frequency_change_pending() {
+        let mut regs = CntControlBase::new_zeroed();
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(!control.frequency_change_pending());
+        control.request_frequency(2);
+        assert!(control.frequency_change_pending());
+
+        regs.cntsr.0 = CntSr(2 << CntSr::FCACK_SHIFT);
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(!control.frequency_change_pending());
+        assert_eq!(2, regs.cntcr.0.fcreq());
+    }
+
+    #[test]
+    fn counter_width() {
+        let mut regs = CntControlBase::new_zeroed();
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(64, control.counter_width());
+        assert_eq!(u64::MAX, control.max_count());
+
+        let control =
+            GenericTimerControl::with_counter_width(UniqueMmioPointer::from(&mut regs), 56);
+        assert_eq!(56, control.counter_width());
+        assert_eq!(0x00ff_ffff_ffff_ffff, control.max_count());
+        assert_eq!(0x20, control.ticks_between(0x00ff_ffff_ffff_fff0, 0x10));
+
+        regs.cntfid[0].0 = 1000;
+        regs.cntcv.0 = 0x00ff_ffff_ffff_fff0;
+        let control =
+            GenericTimerControl::with_counter_width(UniqueMmioPointer::from(&mut regs), 56);
+        let start = control.now();
+        assert_eq!(control.max_count(), control.future_count(Duration::MAX));
+
+        regs.cntcv.0 = 0x10;
+        let control =
+            GenericTimerControl::with_counter_width(UniqueMmioPointer::from(&mut regs), 56);
+        assert_eq!(
+            Duration::from_millis(0x20),
+            control.now().duration_since(start)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn counter_width_invalid() {
+        let mut regs = CntControlBase::new_zeroed();
+        GenericTimerControl::with_counter_width(UniqueMmioPointer::from(&mut regs), 65);
+    }
+
+    #[test]
+    fn self_test() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcv.0 = 0x1234;
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Ok(()), control.self_test(0, 10));
+        assert_eq!(Err(TimerError::CounterStalled), control.self_test(1, 10));
+    }
+
+    #[test]
+    fn is_enabled() {
+        let mut regs = CntControlBase::new_zeroed();
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(!control.is_enabled());
+        control.set_enable(true);
+        assert!(control.is_enabled());
+        control.set_enable(false);
+        assert!(!control.is_enabled());
+    }
+
+    #[test]
+    fn halt_on_debug() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        control.set_halt_on_debug(true);
+        assert!(!control.halted_for_debug());
+        assert_eq!(CntCr::EN | CntCr::HDBG, regs.cntcr.0);
+
+        regs.cntsr.0 = CntSr::HDBG;
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(control.halted_for_debug());
+
+        control.set_halt_on_debug(false);
+        assert_eq!(CntCr::EN, regs.cntcr.0);
+    }
+
+    #[test]
+    fn apply_policy() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cntnsar.0 = CntNsar::NS3;
+        regs.cntacr[3].0 = CntAcr::all();
+
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        ctl.apply_policy(1, FramePolicy::ReadCounterOnly);
+        ctl.apply_policy(2, FramePolicy::ReadCounterAndTimer);
+        ctl.apply_policy(3, FramePolicy::NoAccess);
+        ctl.apply_policy(4, FramePolicy::FullPhysicalVirtual);
+
+        assert_eq!(CntNsar::NS1 | CntNsar::NS2 | CntNsar::NS4, regs.cntnsar.0);
+        assert_eq!(CntAcr::RPCT | CntAcr::RVCT | CntAcr::RFRQ, regs.cntacr[1].0);
+        assert_eq!(
+            CntAcr::RPCT | CntAcr::RVCT | CntAcr::RFRQ | CntAcr::RWPT,
+            regs.cntacr[2].0
+        );
+        assert_eq!(CntAcr::empty(), regs.cntacr[3].0);
+        assert_eq!(CntAcr::all(), regs.cntacr[4].0);
+    }
+
+    #[test]
+    fn frequency_modes_sorted() {
+        let mut regs = CntControlBase::new_zeroed();
+        for (entry, frequency) in regs.cntfid.iter_mut().zip([400, 100, 300, 100, 0, 50]) {
+            entry.0 = frequency;
+        }
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        let mut buffer = [(0, 0); FREQUENCY_MODE_COUNT];
+        assert_eq!(
+            &[(1, 100), (3, 100), (2, 300), (0, 400)],
+            control.frequency_modes_sorted(&mut buffer)
+        );
+
+        let mut buffer = [(0, 0); 2];
+        assert_eq!(
+            &[(1, 100), (0, 400)],
+            control.frequency_modes_sorted(&mut buffer)
+        );
+    }
+
+    #[test]
+    fn request_frequency_acknowledged() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntsr.0 = CntSr(1 << CntSr::FCACK_SHIFT);
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        control.request_frequency_blocking(1);
+        assert_eq!(Ok(()), control.request_frequency_timeout(1, 1));
+        assert_eq!(
+            Err(TimerError::FrequencyNotAcknowledged),
+            control.request_frequency_timeout(2, 10)
+        );
+
+        let mut expected = CntCr::empty();
+        expected.set_fcreq(2);
+        assert_eq!(expected, regs.cntcr.0);
+    }
+
+    #[test]
+    fn frequency_mode_out_of_range() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntfid[FREQUENCY_MODE_COUNT - 1].0 = 1000;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Some(1000), control.frequency_mode(FREQUENCY_MODE_COUNT - 1));
+        assert_eq!(None, control.frequency_mode(FREQUENCY_MODE_COUNT));
+        assert_eq!(Ok(()), control.set_frequency_mode(0, 2000));
+        assert_eq!(
+            Err(TimerError::FrequencyModeUnavailable),
+            control.set_frequency_mode(FREQUENCY_MODE_COUNT, 2000)
+        );
+        assert_eq!(2000, control.base_frequency());
+    }
+
+    #[test]
+    fn frequency_modes() {
+        let mut regs = CntControlBase::new_zeroed();
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(control.frequency_modes().eq([(0, 0)]));
+
+        for (entry, frequency) in regs.cntfid.iter_mut().zip([400, 100, 0, 50]) {
+            entry.0 = frequency;
+        }
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(control.frequency_modes().eq([(0, 400), (1, 100)]));
+
+        for entry in regs.cntfid.iter_mut() {
+            entry.0 = 1000;
+        }
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(FREQUENCY_MODE_COUNT, control.frequency_modes().count());
+    }
+
+    #[test]
+    fn frequency_table() {
+        let mut regs = CntControlBase::new_zeroed();
+        let mut table = [0; FREQUENCY_MODE_COUNT];
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(0, control.frequency_table(&mut table));
+
+        for (entry, frequency) in regs.cntfid.iter_mut().zip([400, 100, 0, 50]) {
+            entry.0 = frequency;
+        }
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(2, control.frequency_table(&mut table));
+        assert_eq!([400, 100, 0], table[..3]);
+
+        let mut short = [0; 1];
+        assert_eq!(1, control.frequency_table(&mut short));
+        assert_eq!([400], short);
+
+        for entry in regs.cntfid.iter_mut() {
+            entry.0 = 1000;
+        }
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(FREQUENCY_MODE_COUNT, control.frequency_table(&mut table));
+        assert_eq!([1000; FREQUENCY_MODE_COUNT], table);
+    }
+
+    #[test]
+    fn frame_out_of_range() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cnttidr.0 = 0x1000_0000;
+
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Ok(Features::IMPLEMENTED), ctl.try_features(7));
+        assert_eq!(Err(TimerError::FrameOutOfRange), ctl.try_features(8));
+        assert_eq!(Ok(()), ctl.try_set_non_secure_access(7, true));
+        assert_eq!(
+            Err(TimerError::FrameOutOfRange),
+            ctl.try_set_non_secure_access(8, true)
+        );
+        assert_eq!(Ok(true), ctl.try_non_secure_access(7));
+        assert_eq!(
+            Err(TimerError::FrameOutOfRange),
+            ctl.try_non_secure_access(8)
+        );
+        assert_eq!(CntNsar::NS7, regs.cntnsar.0);
+    }
+
+    #[test]
+    fn virtual_offset_duration() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cntfrq.0 = 50_000_000;
+
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        ctl.set_virtual_offset_duration(3, Duration::from_millis(2));
+        assert_eq!(100_000, ctl.virtual_offset(3));
+        assert_eq!(Duration::from_millis(2), ctl.virtual_offset_duration(3));
+
+        ctl.set_virtual_offset_duration(3, Duration::from_nanos(9));
+        assert_eq!(0, ctl.virtual_offset(3));
+        ctl.set_virtual_offset_duration(3, Duration::from_nanos(10));
+        assert_eq!(1, ctl.virtual_offset(3));
+    }
+
+    #[test]
+    fn non_secure_mask() {
+        let mut regs = CntCtlBase::new_zeroed();
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+
+        ctl.set_non_secure_mask(0b1010_0101);
+        assert_eq!(0b1010_0101, ctl.non_secure_mask());
+        assert!(ctl.non_secure_access(0));
+        assert!(!ctl.non_secure_access(1));
+        assert_eq!(0b1010_0101, regs.cntnsar.0.bits());
+    }
+
+    #[test]
+    fn non_secure_access_flags() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cntnsar.0 = CntNsar::NS0 | CntNsar::NS5;
+
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(CntNsar::NS0 | CntNsar::NS5, ctl.non_secure_access_flags());
+        assert_eq!("NS5 | NS0", ctl.non_secure_access_flags().to_string());
+
+        ctl.set_non_secure_access_flags(CntNsar::NS7);
+        assert!(ctl.non_secure_access(7));
+        assert_eq!(CntNsar::NS7, regs.cntnsar.0);
+    }
+
+    #[test]
+    fn access_control_out_of_range() {
+        let mut regs = CntCtlBase::new_zeroed();
+        let mut ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+
+        for (index, expected) in [(7, Ok(())), (8, Err(TimerError::FrameOutOfRange))] {
+            assert_eq!(expected, ctl.try_set_access_control(index, CntAcr::RPCT));
+            assert_eq!(
+                expected.map(|()| CntAcr::RPCT),
+                ctl.try_access_control(index)
+            );
+        }
+        assert_eq!(CntAcr::RPCT, regs.cntacr[7].0);
+    }
+
+    #[test]
+    fn elapsed_since() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntfrq.0 = 1000;
+        regs.cntpct.0 = 1500;
+
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Duration::from_millis(1000), frame.elapsed_since(500));
+
+        regs.cntpct.0 = 10;
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Duration::from_millis(20), frame.elapsed_since(u64::MAX - 9));
+    }
+
+    #[test]
+    fn from_base() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntfrq.0 = 1000;
+
+        // SAFETY: `regs` is a valid CntBase frame which is not accessed while `frame` exists.
+        let frame = unsafe { GenericTimerCnt::from_base(NonNull::from(&mut regs)) };
+        assert_eq!(1000, frame.frequency());
+    }
+
+    #[test]
+    fn generic_timer() {
+        let mut control_regs = CntControlBase::new_zeroed();
+        let mut ctl_regs = CntCtlBase::new_zeroed();
+        let mut frame0_regs = CntBase::new_zeroed();
+        let mut frame1_regs = CntBase::new_zeroed();
+        let mut frame2_regs = CntBase::new_zeroed();
+        ctl_regs.cnttidr.0 = 0x0000_0301;
+        frame2_regs.cntfrq.0 = 1000;
+
+        let mut timer = GenericTimer::new(
+            UniqueMmioPointer::from(&mut control_regs),
+            UniqueMmioPointer::from(&mut ctl_regs),
+            [
+                Some(UniqueMmioPointer::from(&mut frame0_regs)),
+                Some(UniqueMmioPointer::from(&mut frame1_regs)),
+                Some(UniqueMmioPointer::from(&mut frame2_regs)),
+                None,
+                None,
+                None,
+                None,
+                None,
+            ],
+        );
+
+        assert!(timer.is_implemented(0));
+        assert!(!timer.is_implemented(1));
+        assert!(timer.is_implemented(2));
+        assert!(!timer.is_implemented(8));
+        assert!(timer.implemented_frames().eq([0, 2]));
+
+        assert!(timer.frame(0).is_some());
+        assert!(timer.frame(1).is_none());
+        assert_eq!(1000, timer.frame(2).unwrap().frequency());
+        assert!(timer.frame(8).is_none());
+
+        timer.control().set_enable(true);
+        timer.ctl().set_frequency(2000);
+
+        assert_eq!(CntCr::EN, control_regs.cntcr.0);
+        assert_eq!(2000, ctl_regs.cntfrq.0);
+    }
+
+    #[test]
+    fn frame_capabilities() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cnttidr.0 = 0x0000_7351;
+
+        let ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(
+            [(false, false), (false, true), (true, false), (true, true)],
+            [0, 1, 2, 3].map(|index| (ctl.has_virtual(index), ctl.has_el0_view(index)))
+        );
+    }
+
+    #[test]
+    fn implemented_frames() {
+        let mut regs = CntCtlBase::new_zeroed();
+        let ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(None, ctl.implemented_frames().next());
+
+        regs.cnttidr.0 = 0x1000_0313;
+        let ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        assert!(ctl.implemented_frames().eq([0, 1, 2, 7]));
+    }
+
+    #[test]
+    fn features() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cnttidr.0 = 0x7531_7531;
+
+        let ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs));
+        let expected = [
+            Features::IMPLEMENTED,
+            Features::IMPLEMENTED | Features::VIRTUAL,
+            Features::IMPLEMENTED | Features::CNTEL0BASE,
+            Features::all(),
+        ];
+        for index in 0..8 {
+            assert_eq!(expected[index % 4], ctl.features(index));
+        }
+    }
+
+    #[test]
+    fn highest_implemented_frame() {
+        let mut regs = CntCtlBase::new_zeroed();
+        assert_eq!(
+            None,
+            GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs)).highest_implemented_frame()
+        );
+
+        regs.cnttidr.0 = 0x0000_0313;
+        assert_eq!(
+            Some(2),
+            GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs)).highest_implemented_frame()
+        );
+
+        regs.cnttidr.0 = 0x1000_1000;
+        assert_eq!(
+            Some(7),
+            GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs)).highest_implemented_frame()
+        );
+    }
+
+    #[test]
+    fn frequency_nonzero() {
+        let mut regs = CntBase::new_zeroed();
+        let cnt = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(None, cnt.frequency_nonzero());
+
+        regs.cntfrq.0 = 1000;
+        let cnt = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(NonZeroU32::new(1000), cnt.frequency_nonzero());
+        assert_eq!(Duration::from_millis(1500), cnt.ticks_to_duration(1500));
+        assert_eq!(250, cnt.duration_to_ticks(Duration::from_millis(250)));
+
+        let mut regs = CntEl0Base::new_zeroed();
+        regs.cntfrq.0 = 2000;
+        let cnt_el0 = GenericTimerCntEl0::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(NonZeroU32::new(2000), cnt_el0.frequency_nonzero());
+    }
+
+    #[test]
+    fn spins_for() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntfid[0].0 = 1_000_000;
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(1000, control.spins_for(Duration::from_millis(1), 1));
+        assert_eq!(250, control.spins_for(Duration::from_millis(1), 4));
+        assert_eq!(334, control.spins_for(Duration::from_millis(1), 3));
+        assert_eq!(1000, control.spins_for(Duration::from_millis(1), 0));
+        assert_eq!(2, control.spins_for(Duration::from_nanos(1001), 1));
+    }
+
+    #[test]
+    fn future_count() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntfid[0].0 = 1_000_000;
+        regs.cntcv.0 = 5000;
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(5000, control.future_count(Duration::ZERO));
+        assert_eq!(6000, control.future_count(Duration::from_millis(1)));
+        assert_eq!(u64::MAX, control.future_count(Duration::MAX));
+    }
+
+    #[test]
+    fn count_trace() {
+        let mut regs = CntControlBase::new_zeroed();
+        let mut trace = CountTrace::<3>::new();
+        assert!(trace.is_empty());
+        assert_eq!(None, trace.iter().next());
+
+        for count in [10, 20, 30, 40] {
+            regs.cntcv.0 = count;
+            trace.record(&GenericTimerControl::new(UniqueMmioPointer::from(
+                &mut regs,
+            )));
+        }
+
+        assert_eq!(3, trace.len());
+        assert!(trace.iter().eq([40, 30, 20]));
+
+        let mut trace = CountTrace::<0>::new();
+        trace.record(&GenericTimerControl::new(UniqueMmioPointer::from(
+            &mut regs,
+        )));
+        assert!(trace.is_empty());
+    }
+
+    #[test]
+    fn effective_frequency() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntfid[0].0 = 1_000_000;
+        regs.cntscr.0 = 0x0280_0000;
+        assert_eq!(
+            1_000_000,
+            GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).effective_frequency()
+        );
+
+        regs.cntcr.0 = CntCr::SCEN;
+        assert_eq!(
+            2_500_000,
+            GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).effective_frequency()
+        );
+
+        regs.cntfid[0].0 = u32::MAX;
+        assert_eq!(
+            u32::MAX,
+            GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).effective_frequency()
+        );
+    }
+
+    #[test]
+    fn scale_decoded() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntscr.0 = 0x0180_0000;
+
+        let control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Scale::from_bits(0x0180_0000), control.scale_decoded());
+        assert_eq!(1.5, control.scale_as_f64());
+        assert_eq!((0x0180_0000, 0x0100_0000), control.scale_ratio());
+        assert_eq!(1.0, Scale::ONE.as_f64());
+    }
+
+    #[test]
+    fn counter_id() {
+        let counter_id = CounterId::from_registers([
+            0x04, 0x00, 0x00, 0x00, 0x01, 0xb1, 0x3b, 0x00, 0x0d, 0xf0, 0x05, 0xb1,
+        ]);
+        assert_eq!(0x0000_0004_003b_b101, counter_id.peripheral_id());
+        assert_eq!(0xb105_f00d, counter_id.component_id());
+        assert_eq!(Some(Jep106Id::ARM), counter_id.manufacturer());
+        assert!(counter_id.is_valid());
+
+        let mut regs = CntControlBase::new_zeroed();
+        assert_eq!(
+            Some(TimerError::ComponentMismatch),
+            GenericTimerControl::try_new(UniqueMmioPointer::from(&mut regs)).err()
+        );
+
+        for (register, value) in regs.counter_id.iter_mut().zip(counter_id.registers()) {
+            register.0 = value;
+        }
+        let control = GenericTimerControl::try_new(UniqueMmioPointer::from(&mut regs)).unwrap();
+        assert_eq!(counter_id, control.counter_id());
+        assert_eq!(Some(Jep106Id::ARM), control.manufacturer());
+
+        let legacy = CounterId::from_registers([0; COUNTER_ID_COUNT]);
+        assert_eq!(None, legacy.manufacturer());
+    }
+
+    #[test]
+    fn capture() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntpct.0 = 0x1_0000;
+        regs.cntvct.0 = 0x0_8000;
+        regs.cntfrq.0 = 1_000_000;
+        regs.cntel0acr.0 = CntEl0Acr::EL0PCTEN;
+        regs.cntvoff.0 = 0x8000;
+        regs.cntp.cval.0 = 0x1_1000;
+        regs.cntp.tval.0 = 0x1000;
+        regs.cntp.ctl.0 = TimerControl::ENABLE;
+        regs.cntv.ctl.0 = TimerControl::IMASK;
+
+        let capture = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs)).capture();
+        assert_eq!(0x8000, capture.virtual_offset);
+        assert_eq!(
+            TimerRegsSnapshot {
+                compare_value: 0x1_1000,
+                timer_value: 0x1000,
+                control: TimerControl::ENABLE,
+            },
+            capture.physical_timer
+        );
+
+        let bytes = capture.as_bytes();
+        let replayed = FrameCapture::read_from_bytes(bytes).unwrap();
+        let mut replayed_regs = CntBase::from_capture(&replayed);
+        assert!(regs == replayed_regs);
+
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut replayed_regs));
+        assert_eq!(capture, frame.capture());
+    }
+
+    #[test]
+    fn control_capture() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN | CntCr::SCEN;
+        regs.cntsr.0 = CntSr::HDBG;
+        regs.cntcv.0 = 0x1234;
+        regs.cntscr.0 = 0x0100_0000;
+        regs.cntid.0 = CntId(CntId::CNTSC_IMPLEMENTED);
+        regs.cntfid[0].0 = 50_000_000;
+        regs.cntfid[39].0 = 1000;
+
+        let capture = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs)).capture();
+        assert_eq!(CntCr::EN | CntCr::SCEN, capture.control);
+        assert_eq!(CntSr::HDBG, capture.status);
+        assert_eq!(0x1234, capture.count);
+        assert_eq!(0x0100_0000, capture.scale);
+        assert!(capture.id.scaling_implemented());
+        assert_eq!(50_000_000, capture.frequency_modes[0]);
+        assert_eq!(1000, capture.frequency_modes[39]);
+    }
+
+    #[test]
+    fn ctl_capture() {
+        let mut regs = CntCtlBase::new_zeroed();
+        regs.cntfrq.0 = 1_000_000;
+        regs.cntnsar.0 = CntNsar::NS1;
+        regs.cnttidr.0 = 0x0000_0031;
+        regs.cntacr[1].0 = CntAcr::all();
+        regs.cntvoff[1].0 = 0x8000;
+
+        let capture = GenericTimerCtl::new(UniqueMmioPointer::from(&mut regs)).capture();
+        assert_eq!(1_000_000, capture.frequency);
+        assert_eq!(CntNsar::NS1, capture.non_secure_access);
+        assert_eq!(0x31, capture.timer_id);
+        assert_eq!(CntAcr::all(), capture.access_control[1]);
+        assert_eq!(CntAcr::empty(), capture.access_control[0]);
+        assert_eq!(0x8000, capture.virtual_offset[1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut control_regs = CntControlBase::new_zeroed();
+        control_regs.cntcr.0 = CntCr::EN;
+        control_regs.cntcv.0 = 0x1234;
+        for (index, entry) in control_regs.cntfid.iter_mut().enumerate() {
+            entry.0 = 1000 * (index as u32 + 1);
+        }
+        let control =
+            GenericTimerControl::new(UniqueMmioPointer::from(&mut control_regs)).capture();
+        let json = serde_json::to_string(&control).unwrap();
+        assert_eq!(control, serde_json::from_str(&json).unwrap());
+
+        let mut ctl_regs = CntCtlBase::new_zeroed();
+        ctl_regs.cntnsar.0 = CntNsar::NS0 | CntNsar::NS7;
+        ctl_regs.cntacr[7].0 = CntAcr::RPCT;
+        ctl_regs.cntvoff[7].0 = u64::MAX;
+        let ctl = GenericTimerCtl::new(UniqueMmioPointer::from(&mut ctl_regs)).capture();
+        let json = serde_json::to_string(&ctl).unwrap();
+        assert_eq!(ctl, serde_json::from_str(&json).unwrap());
+
+        let mut frame_regs = CntBase::new_zeroed();
+        frame_regs.cntp.cval.0 = 0x1_1000;
+        frame_regs.cntp.ctl.0 = TimerControl::ENABLE | TimerControl::ISTATUS;
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut frame_regs)).capture();
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(frame, serde_json::from_str(&json).unwrap());
+
+        assert!(serde_json::from_str::<ControlCapture>("{\"control\":0}").is_err());
+    }
+
+    #[test]
+    fn physical_virtual_conversion() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntvoff.0 = 0x8000;
+
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(0x8000, frame.physical_to_virtual(0x1_0000));
+        assert_eq!(0x1_0000, frame.virtual_to_physical(0x8000));
+        assert_eq!(u64::MAX - 0x7fff, frame.physical_to_virtual(0));
+        assert_eq!(0, frame.virtual_to_physical(u64::MAX - 0x7fff));
+    }
+
+    /// Runs a full arm and expire cycle of a one-shot timer against an in-memory CNTBase frame,
+    /// with the test playing the role of the hardware by advancing the count and setting ISTATUS.
+    #[test]
+    fn one_shot_cycle() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntfrq.0 = 1_000_000;
+        regs.cntpct.0 = 10_000;
+
+        let mut frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        frame
+            .physical_timer()
+            .arm_one_shot(Duration::from_millis(5));
+        assert_eq!(15_000, regs.cntp.cval.0);
+        assert_eq!(TimerControl::ENABLE, regs.cntp.ctl.0);
+
+        // The count passes the deadline and the hardware signals the timer condition.
+        regs.cntpct.0 = 15_001;
+        regs.cntp.ctl.0 |= TimerControl::ISTATUS;
+
+        let mut frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        let mut timer = frame.physical_timer();
+        assert!(timer.is_expired());
+        assert_eq!(None, timer.remaining());
+
+        timer.handle_one_shot();
+        assert!(!timer.is_expired());
+        assert_eq!(TimerControl::empty(), regs.cntp.ctl.0);
+    }
+
+    #[test]
+    fn virtual_timer_deadline() {
+        let mut regs = CntBase::new_zeroed();
+        regs.cntpct.0 = 0x1_0000;
+        regs.cntvct.0 = 0x0_8000;
+        regs.cntvoff.0 = 0x8000;
+        regs.cntfrq.0 = 1_000_000;
+
+        let mut frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(0x0_8000, frame.virtual_timer().base_count());
+        assert_eq!(0x1_0000, frame.physical_timer().base_count());
+
+        frame.virtual_timer().set_deadline(Duration::from_millis(5));
+        frame
+            .physical_timer()
+            .set_deadline(Duration::from_millis(5));
+
+        assert_eq!(0x0_8000 + 5000, regs.cntv.cval.0);
+        assert_eq!(0x1_0000 + 5000, regs.cntp.cval.0);
+    }
+
+    #[test]
+    fn enable_scaling() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(
+            Err(TimerError::ScalingUnsupported),
+            control.enable_scaling(0x0200_0000)
+        );
+        assert_eq!(CntCr::EN, regs.cntcr.0);
+        assert_eq!(0, regs.cntscr.0);
+
+        regs.cntid.0 = CntId(CntId::CNTSC_IMPLEMENTED);
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Ok(()), control.enable_scaling(0x0200_0000));
+        assert_eq!(CntCr::EN | CntCr::SCEN, regs.cntcr.0);
+        assert_eq!(0x0200_0000, regs.cntscr.0);
+    }
+
+    #[test]
+    fn disable_scaling() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntcr.0 = CntCr::EN | CntCr::SCEN;
+        regs.cntscr.0 = 0x0200_0000;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        control.disable_scaling();
+        control.disable_scaling();
+
+        assert_eq!(CntCr::EN, regs.cntcr.0);
+        assert_eq!(0, regs.cntscr.0);
+    }
+
+    #[test]
+    fn scaling_snapshot_restore() {
+        let mut regs = CntControlBase::new_zeroed();
+        regs.cntsr.0 = CntSr(2 << CntSr::FCACK_SHIFT);
+        regs.cntscr.0 = 0x0200_0000;
+        regs.cntcr.0 = CntCr::EN | CntCr::SCEN;
+
+        let mut control = GenericTimerControl::new(UniqueMmioPointer::from(&mut regs));
+        let state = control.scaling_snapshot();
+        assert_eq!(
+            ScalingState {
+                scale: Scale::from_bits(0x0200_0000),
+                scaling_enabled: true,
+                frequency_index: 2,
+            },
+            state
+        );
+
+        control.disable_scaling();
+        control.request_frequency(0);
+        control.restore_scaling(&state);
+
+        let mut expected = CntCr::EN | CntCr::SCEN;
+        expected.set_fcreq(2);
+        assert_eq!(expected, regs.cntcr.0);
+        assert_eq!(0x0200_0000, regs.cntscr.0);
+    }
+
+    #[test]
+    fn pending_frames() {
+        let mut regs = [
+            CntBase::new_zeroed(),
+            CntBase::new_zeroed(),
+            CntBase::new_zeroed(),
+        ];
+        regs[0].cntp.ctl.0 = TimerControl::ISTATUS;
+        regs[1].cntv.ctl.0 = TimerControl::ENABLE | TimerControl::IMASK | TimerControl::ISTATUS;
+        regs[2].cntp.ctl.0 = TimerControl::ENABLE | TimerControl::ISTATUS;
+        regs[2].cntv.ctl.0 = TimerControl::ENABLE;
+
+        let frames = regs
+            .each_mut()
+            .map(|regs| GenericTimerCnt::new(UniqueMmioPointer::from(regs)));
+        assert_eq!((false, false), frames[0].pending_timers());
+        assert_eq!((false, true), frames[1].pending_timers());
+        assert_eq!((true, false), frames[2].pending_timers());
+        assert_eq!(0b110, GenericTimerCnt::pending_frames(&frames));
+    }
+
+    #[test]
+    fn allow_el0_access() {
+        let mut regs = CntBase::new_zeroed();
+        let mut frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+
+        frame.allow_el0_physical_count(true);
+        frame.allow_el0_virtual_count(true);
+        frame.allow_el0_physical_timer(true);
+        frame.allow_el0_virtual_timer(true);
+        assert_eq!(CntEl0Acr::all(), frame.el0_access());
+
+        frame.allow_el0_virtual_count(false);
+        frame.allow_el0_physical_timer(false);
+        assert_eq!(CntEl0Acr::EL0PCTEN | CntEl0Acr::EL0VTEN, regs.cntel0acr.0);
+    }
+
+    #[test]
+    fn el0_view_access() {
+        let mut regs = CntBase::new_zeroed();
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(El0Access::default(), frame.el0_view_access());
+
+        regs.cntel0acr.0 = CntEl0Acr::EL0VCTEN | CntEl0Acr::EL0PTEN;
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(
+            El0Access {
+                physical_counter: false,
+                virtual_counter: true,
+                frequency: true,
+                physical_timer: true,
+                virtual_timer: false,
+            },
+            frame.el0_view_access()
+        );
+    }
+
+    #[test]
+    fn read_frame() {
+        let mut regs = CntReadBase::new_zeroed();
+        regs.cntcv.0 = 0x1234;
+        regs.counter_id[8].0 = 0x0d;
+        regs.counter_id[9].0 = 0xf0;
+        regs.counter_id[10].0 = 0x05;
+        regs.counter_id[11].0 = 0xb1;
+
+        let read = GenericTimerRead::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(0x1234, read.count());
+        assert_eq!(0xb105_f00d, read.component_id());
+    }
+
+    #[test]
+    fn timer_control_constructors() {
+        assert_eq!(TimerControl::ENABLE, TimerControl::enabled_with_interrupt());
+        assert_eq!(
+            TimerControl::ENABLE | TimerControl::IMASK,
+            TimerControl::enabled_polling()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0x0", TimerControl::empty().to_string());
+        assert_eq!("0x0", CntNsar::empty().to_string());
+        assert_eq!(
+            "ISTATUS | ENABLE",
+            (TimerControl::ENABLE | TimerControl::ISTATUS).to_string()
+        );
+        assert_eq!("RPCT | 0x100", CntAcr::from_bits_retain(0x101).to_string());
+    }
+
+    #[test]
+    fn frame_component() {
+        let mut regs = CntBase::new_zeroed();
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(Err(TimerError::ComponentMismatch), frame.verify_component());
+
+        for (register, value) in regs.counter_id.iter_mut().zip([
+            0x04, 0x00, 0x00, 0x00, 0x01, 0xb1, 0x3b, 0x00, 0x0d, 0xf0, 0x05, 0xb1,
+        ]) {
+            register.0 = value;
+        }
+        let frame = GenericTimerCnt::new(UniqueMmioPointer::from(&mut regs));
+        assert_eq!(0x0000_0004_003b_b101, frame.peripheral_id());
+        assert_eq!(0xb105_f00d, frame.component_id());
+        assert_eq!(Ok(()), frame.verify_component());
+    }
 }
@@ -5,20 +5,131 @@
 //!
 //! See D24.10 Generic Timer registers.
 
-use crate::TimerInterface;
+#[cfg(feature = "el1")]
+use crate::memory_mapped::CntEl0Acr;
+use crate::{TimerInterface, memory_mapped::TimerControl};
+use arm_sysregs::{
+    CntfrqEl0, CntpCtlEl0, CntpCvalEl0, CntpTvalEl0, CntvCtlEl0, CntvCvalEl0, CntvTvalEl0,
+    read_cntfrq_el0, read_cntp_ctl_el0, read_cntp_cval_el0, read_cntp_tval_el0, read_cntpct_el0,
+    read_cntv_ctl_el0, read_cntv_cval_el0, read_cntv_tval_el0, read_cntvct_el0, write_cntfrq_el0,
+    write_cntp_ctl_el0, write_cntp_cval_el0, write_cntp_tval_el0, write_cntv_ctl_el0,
+    write_cntv_cval_el0, write_cntv_tval_el0,
+};
 #[cfg(feature = "el2")]
 use arm_sysregs::{
-    CnthpCtlEl2, CnthpsCtlEl2, CnthvCtlEl2, CnthvsCtlEl2, read_cnthp_ctl_el2, read_cnthp_tval_el2,
-    read_cnthps_ctl_el2, read_cnthps_tval_el2, read_cnthv_ctl_el2, read_cnthv_tval_el2,
-    read_cnthvs_ctl_el2, read_cnthvs_tval_el2, write_cnthp_ctl_el2, write_cnthps_ctl_el2,
-    write_cnthv_ctl_el2, write_cnthvs_ctl_el2,
+    CnthpCtlEl2, CnthpCvalEl2, CnthpTvalEl2, CnthpsCtlEl2, CnthpsCvalEl2, CnthpsTvalEl2,
+    CnthvCtlEl2, CnthvCvalEl2, CnthvTvalEl2, CnthvsCtlEl2, CnthvsCvalEl2, CnthvsTvalEl2,
+    read_cnthp_ctl_el2, read_cnthp_cval_el2, read_cnthp_tval_el2, read_cnthps_ctl_el2,
+    read_cnthps_cval_el2, read_cnthps_tval_el2, read_cnthv_ctl_el2, read_cnthv_cval_el2,
+    read_cnthv_tval_el2, read_cnthvs_ctl_el2, read_cnthvs_cval_el2, read_cnthvs_tval_el2,
+    write_cnthp_ctl_el2, write_cnthp_cval_el2, write_cnthp_tval_el2, write_cnthps_ctl_el2,
+    write_cnthps_cval_el2, write_cnthps_tval_el2, write_cnthv_ctl_el2, write_cnthv_cval_el2,
+    write_cnthv_tval_el2, write_cnthvs_ctl_el2, write_cnthvs_cval_el2, write_cnthvs_tval_el2,
 };
+#[cfg(feature = "el1")]
 use arm_sysregs::{
-    CntpCtlEl0, CntvCtlEl0, read_cntfrq_el0, read_cntp_ctl_el0, read_cntp_tval_el0,
-    read_cntv_ctl_el0, read_cntv_tval_el0, write_cntp_ctl_el0, write_cntv_ctl_el0,
+    CntpsCtlEl1, CntpsCvalEl1, CntpsTvalEl1, read_cntps_ctl_el1, read_cntps_cval_el1,
+    read_cntps_tval_el1, write_cntps_ctl_el1, write_cntps_cval_el1, write_cntps_tval_el1,
 };
+
+/// Counter-timer Kernel Control Register, which controls the EL0 access to the counters and timers.
+#[cfg(feature = "el1")]
+pub use arm_sysregs::{CntkctlEl1, read_cntkctl_el1, write_cntkctl_el1};
+
 #[cfg(feature = "el1")]
-use arm_sysregs::{CntpsCtlEl1, read_cntps_ctl_el1, read_cntps_tval_el1, write_cntps_ctl_el1};
+impl From<CntEl0Acr> for CntkctlEl1 {
+    /// Converts the EL0 access control bits of a memory mapped frame to the equivalent bits of
+    /// `CNTKCTL_EL1`.
+    fn from(cntel0acr: CntEl0Acr) -> Self {
+        let mut cntkctl = Self::empty();
+        cntkctl.set(Self::EL0PCTEN, cntel0acr.contains(CntEl0Acr::EL0PCTEN));
+        cntkctl.set(Self::EL0VCTEN, cntel0acr.contains(CntEl0Acr::EL0VCTEN));
+        cntkctl.set(Self::EL0VTEN, cntel0acr.contains(CntEl0Acr::EL0VTEN));
+        cntkctl.set(Self::EL0PTEN, cntel0acr.contains(CntEl0Acr::EL0PTEN));
+        cntkctl
+    }
+}
+
+/// Counter-timer Hypervisor Control Register, which controls the EL1 and EL0 access to the
+/// physical counter and timer, and the FEAT_ECV traps and offsets.
+#[cfg(feature = "el2")]
+pub use arm_sysregs::{CnthctlEl2, read_cnthctl_el2, write_cnthctl_el2};
+
+/// Implements `TimerInterface` for a system register based timer using the given count source and
+/// control, compare value and timer value registers.
+macro_rules! impl_timer_interface {
+    (
+        $timer:ty,
+        count: $count:ident,
+        ctl: ($ctl:ident, $read_ctl:ident, $write_ctl:ident),
+        cval: ($cval:ident, $read_cval:ident, $write_cval:ident),
+        tval: ($tval:ident, $read_tval:ident, $write_tval:ident) $(,)?
+    ) => {
+        impl TimerInterface for $timer {
+            fn enable(&mut self) {
+                let control = $read_ctl();
+                $write_ctl(control | $ctl::ENABLE);
+            }
+
+            fn frequency(&self) -> u32 {
+                counter_frequency()
+            }
+
+            fn timer_value(&self) -> u32 {
+                $read_tval().timervalue()
+            }
+
+            fn set_timer_value(&mut self, value: u32) {
+                $write_tval($tval::from_bits_retain(value.into()));
+            }
+
+            fn count(&self) -> u64 {
+                $count()
+            }
+
+            fn compare_value(&self) -> u64 {
+                $read_cval().comparevalue()
+            }
+
+            fn set_compare_value(&mut self, value: u64) {
+                $write_cval($cval::from_bits_retain(value));
+            }
+
+            fn control(&self) -> TimerControl {
+                TimerControl::from_bits_truncate($read_ctl().bits() as u32)
+            }
+
+            fn set_control(&mut self, control: TimerControl) {
+                $write_ctl($ctl::from_bits_truncate(control.bits().into()));
+            }
+        }
+    };
+}
+
+/// Reads the counter frequency in Hz from `CNTFRQ_EL0`, as programmed by the boot firmware.
+pub fn counter_frequency() -> u32 {
+    read_cntfrq_el0().clockfreq()
+}
+
+/// Writes the counter frequency in Hz to `CNTFRQ_EL0`.
+///
+/// The register is only writable at the highest implemented Exception level. It doesn't change the
+/// frequency of the counter, it only informs software about it.
+pub fn set_counter_frequency(frequency: u32) {
+    let mut cntfrq = CntfrqEl0::empty();
+    cntfrq.set_clockfreq(frequency);
+    write_cntfrq_el0(cntfrq);
+}
+
+/// Reads the physical count from `CNTPCT_EL0`.
+fn physical_count() -> u64 {
+    read_cntpct_el0().physicalcount()
+}
+
+/// Reads the virtual count from `CNTVCT_EL0`.
+fn virtual_count() -> u64 {
+    read_cntvct_el0().virtualcount()
+}
 
 /// Physical Secure Timer
 ///
@@ -27,20 +138,13 @@ use arm_sysregs::{CntpsCtlEl1, read_cntps_ctl_el1, read_cntps_tval_el1, write_cn
 pub struct PhysicalSecureTimer;
 
 #[cfg(feature = "el1")]
-impl TimerInterface for PhysicalSecureTimer {
-    fn enable(&mut self) {
-        let control = read_cntps_ctl_el1();
-        write_cntps_ctl_el1(control | CntpsCtlEl1::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cntps_tval_el1().timervalue()
-    }
-}
+impl_timer_interface!(
+    PhysicalSecureTimer,
+    count: physical_count,
+    ctl: (CntpsCtlEl1, read_cntps_ctl_el1, write_cntps_ctl_el1),
+    cval: (CntpsCvalEl1, read_cntps_cval_el1, write_cntps_cval_el1),
+    tval: (CntpsTvalEl1, read_cntps_tval_el1, write_cntps_tval_el1),
+);
 
 /// Hypervisor Physical Timer
 ///
@@ -49,20 +153,13 @@ impl TimerInterface for PhysicalSecureTimer {
 pub struct HypervisorPhysicalTimer;
 
 #[cfg(feature = "el2")]
-impl TimerInterface for HypervisorPhysicalTimer {
-    fn enable(&mut self) {
-        let control = read_cnthp_ctl_el2();
-        write_cnthp_ctl_el2(control | CnthpCtlEl2::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cnthp_tval_el2().timervalue()
-    }
-}
+impl_timer_interface!(
+    HypervisorPhysicalTimer,
+    count: physical_count,
+    ctl: (CnthpCtlEl2, read_cnthp_ctl_el2, write_cnthp_ctl_el2),
+    cval: (CnthpCvalEl2, read_cnthp_cval_el2, write_cnthp_cval_el2),
+    tval: (CnthpTvalEl2, read_cnthp_tval_el2, write_cnthp_tval_el2),
+);
 
 /// Secure EL2 Physical Timer
 ///
@@ -71,20 +168,13 @@ impl TimerInterface for HypervisorPhysicalTimer {
 pub struct SecureEl2PhysicalTimer;
 
 #[cfg(feature = "el2")]
-impl TimerInterface for SecureEl2PhysicalTimer {
-    fn enable(&mut self) {
-        let control = read_cnthps_ctl_el2();
-        write_cnthps_ctl_el2(control | CnthpsCtlEl2::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cnthps_tval_el2().timervalue()
-    }
-}
+impl_timer_interface!(
+    SecureEl2PhysicalTimer,
+    count: physical_count,
+    ctl: (CnthpsCtlEl2, read_cnthps_ctl_el2, write_cnthps_ctl_el2),
+    cval: (CnthpsCvalEl2, read_cnthps_cval_el2, write_cnthps_cval_el2),
+    tval: (CnthpsTvalEl2, read_cnthps_tval_el2, write_cnthps_tval_el2),
+);
 
 /// EL2 Virtual Timer
 ///
@@ -93,20 +183,13 @@ impl TimerInterface for SecureEl2PhysicalTimer {
 pub struct El2VirtualTimer;
 
 #[cfg(feature = "el2")]
-impl TimerInterface for El2VirtualTimer {
-    fn enable(&mut self) {
-        let control = read_cnthv_ctl_el2();
-        write_cnthv_ctl_el2(control | CnthvCtlEl2::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cnthv_tval_el2().timervalue()
-    }
-}
+impl_timer_interface!(
+    El2VirtualTimer,
+    count: virtual_count,
+    ctl: (CnthvCtlEl2, read_cnthv_ctl_el2, write_cnthv_ctl_el2),
+    cval: (CnthvCvalEl2, read_cnthv_cval_el2, write_cnthv_cval_el2),
+    tval: (CnthvTvalEl2, read_cnthv_tval_el2, write_cnthv_tval_el2),
+);
 
 /// Secure EL2 Virtual Timer
 ///
@@ -115,57 +198,37 @@ impl TimerInterface for El2VirtualTimer {
 pub struct SecureEl2VirtualTimer;
 
 #[cfg(feature = "el2")]
-impl TimerInterface for SecureEl2VirtualTimer {
-    fn enable(&mut self) {
-        let control = read_cnthvs_ctl_el2();
-        write_cnthvs_ctl_el2(control | CnthvsCtlEl2::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cnthvs_tval_el2().timervalue()
-    }
-}
+impl_timer_interface!(
+    SecureEl2VirtualTimer,
+    count: virtual_count,
+    ctl: (CnthvsCtlEl2, read_cnthvs_ctl_el2, write_cnthvs_ctl_el2),
+    cval: (CnthvsCvalEl2, read_cnthvs_cval_el2, write_cnthvs_cval_el2),
+    tval: (CnthvsTvalEl2, read_cnthvs_tval_el2, write_cnthvs_tval_el2),
+);
 
 /// Physical Timer
 ///
 /// Uses `CNTP_*` system registers.
 pub struct PhysicalTimer;
 
-impl TimerInterface for PhysicalTimer {
-    fn enable(&mut self) {
-        let control = read_cntp_ctl_el0();
-        write_cntp_ctl_el0(control | CntpCtlEl0::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cntp_tval_el0().timervalue()
-    }
-}
+impl_timer_interface!(
+    PhysicalTimer,
+    count: physical_count,
+    ctl: (CntpCtlEl0, read_cntp_ctl_el0, write_cntp_ctl_el0),
+    cval: (CntpCvalEl0, read_cntp_cval_el0, write_cntp_cval_el0),
+    tval: (CntpTvalEl0, read_cntp_tval_el0, write_cntp_tval_el0),
+);
 
 /// Virtual Timer
 ///
-/// Uses `CNTV_*` system registers.
+/// Uses `CNTV_*` system registers. The timer condition and relative deadlines use `CNTVCT_EL0`,
+/// which the hardware already offsets by `CNTVOFF_EL2`, so no offset has to be applied by software.
 pub struct VirtualTimer;
 
-impl TimerInterface for VirtualTimer {
-    fn enable(&mut self) {
-        let control = read_cntv_ctl_el0();
-        write_cntv_ctl_el0(control | CntvCtlEl0::ENABLE);
-    }
-
-    fn frequency(&self) -> u32 {
-        read_cntfrq_el0().clockfreq()
-    }
-
-    fn timer_value(&self) -> u32 {
-        read_cntv_tval_el0().timervalue()
-    }
-}
+impl_timer_interface!(
+    VirtualTimer,
+    count: virtual_count,
+    ctl: (CntvCtlEl0, read_cntv_ctl_el0, write_cntv_ctl_el0),
+    cval: (CntvCvalEl0, read_cntv_cval_el0, write_cntv_cval_el0),
+    tval: (CntvTvalEl0, read_cntv_tval_el0, write_cntv_tval_el0),
+);